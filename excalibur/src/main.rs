@@ -1,5 +1,7 @@
 use crate::app::App;
 use crate::modules::ModuleId;
+#[cfg(target_os = "linux")]
+use crate::modules::proctrace::{ExportFormat as ProcessTracerFormat, SectionFilter};
 use clap::{Parser, Subcommand};
 use std::fs::OpenOptions;
 
@@ -26,7 +28,26 @@ enum Commands {
     /// Inspect running processes and their supervisors (Linux only)
     #[cfg(target_os = "linux")]
     #[command(visible_alias = "pt")]
-    ProcessTracer,
+    ProcessTracer {
+        /// Query to run without the TUI (e.g. "nginx", ":8080", "user:root AND cpu>50")
+        query: Vec<String>,
+
+        /// Print full results in this format instead of a compact table
+        #[arg(long, value_enum)]
+        format: Option<ProcessTracerFormat>,
+
+        /// Include environment variables (secret values masked)
+        #[arg(long)]
+        env: bool,
+
+        /// Include network bindings and Unix sockets
+        #[arg(long)]
+        net: bool,
+
+        /// Include the ancestor chain
+        #[arg(long)]
+        tree: bool,
+    },
 
     /// Switch Claude Code settings profiles
     #[command(visible_alias = "s")]
@@ -43,7 +64,21 @@ fn main() -> color_eyre::Result<()> {
     let initial_module = match cli.command {
         Some(Commands::History) => Some(ModuleId::History),
         #[cfg(target_os = "linux")]
-        Some(Commands::ProcessTracer) => Some(ModuleId::ProcessTracer),
+        Some(Commands::ProcessTracer {
+            query,
+            format,
+            env,
+            net,
+            tree,
+        }) => {
+            // A query runs without the TUI (no TTY needed); exit code tells whether anything matched
+            if !query.is_empty() {
+                let sections = SectionFilter { env, net, tree };
+                let code = crate::modules::proctrace::run_query(&query.join(" "), format, sections);
+                std::process::exit(code);
+            }
+            Some(ModuleId::ProcessTracer)
+        }
         Some(Commands::Settings) => Some(ModuleId::Settings),
         None => None,
     };
@@ -1,4 +1,4 @@
-mod clipboard;
+pub(crate) mod clipboard;
 mod parser;
 mod state;
 mod ui;
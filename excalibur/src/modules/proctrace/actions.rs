@@ -0,0 +1,180 @@
+use super::collector::read_start_ticks;
+use super::systemd::SYSTEMCTL;
+use color_eyre::{Result, eyre::eyre};
+use std::io;
+use std::process::Command;
+
+/// Signals offered by the signal picker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+    Term,
+    Kill,
+    Hup,
+    Stop,
+    Cont,
+}
+
+impl Signal {
+    /// All signals in picker order
+    pub const ALL: [Signal; 5] = [
+        Signal::Term,
+        Signal::Kill,
+        Signal::Hup,
+        Signal::Stop,
+        Signal::Cont,
+    ];
+
+    /// Get signal name (e.g., "SIGTERM")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+            Signal::Hup => "SIGHUP",
+            Signal::Stop => "SIGSTOP",
+            Signal::Cont => "SIGCONT",
+        }
+    }
+
+    /// Get a short description for the picker
+    pub fn description(&self) -> &'static str {
+        match self {
+            Signal::Term => "Ask the process to terminate",
+            Signal::Kill => "Kill immediately (cannot be caught)",
+            Signal::Hup => "Hang up / reload configuration",
+            Signal::Stop => "Pause execution",
+            Signal::Cont => "Resume a stopped process",
+        }
+    }
+
+    fn number(&self) -> libc::c_int {
+        match self {
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Hup => libc::SIGHUP,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Cont => libc::SIGCONT,
+        }
+    }
+}
+
+/// Action that can be taken on a process from the results view
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessAction {
+    Signal(Signal),
+    Renice(i32),
+    RestartUnit(String),
+    StopUnit(String),
+}
+
+impl ProcessAction {
+    /// Describe the action for the confirmation dialog
+    pub fn describe(&self) -> String {
+        match self {
+            ProcessAction::Signal(sig) => format!("Send {}", sig.name()),
+            ProcessAction::Renice(nice) => format!("Renice to {}", nice),
+            ProcessAction::RestartUnit(unit) => format!("systemctl restart {}", unit),
+            ProcessAction::StopUnit(unit) => format!("systemctl stop {}", unit),
+        }
+    }
+}
+
+/// Action waiting for confirmation, with the target shown in the dialog
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    pub pid: u32,
+    pub start_ticks: u64, // identifies the process across PID reuse
+    pub command: String,
+    pub user: String,
+    pub action: ProcessAction,
+}
+
+/// Run a confirmed action, returning a message for the notification
+pub fn execute_action(pending: &PendingAction) -> Result<String> {
+    let pid = pending.pid;
+
+    // The PID may have been reused while the dialog was open or the result was stale
+    if matches!(
+        pending.action,
+        ProcessAction::Signal(_) | ProcessAction::Renice(_)
+    ) {
+        match read_start_ticks(pid) {
+            Some(start_ticks) if start_ticks == pending.start_ticks => {}
+            Some(_) => {
+                return Err(eyre!(
+                    "PID {} now belongs to a different process; action aborted",
+                    pid
+                ));
+            }
+            None => return Err(eyre!("PID {} no longer exists", pid)),
+        }
+    }
+
+    match &pending.action {
+        ProcessAction::Signal(sig) => {
+            // SAFETY: kill(2) has no memory-safety preconditions
+            let ret = unsafe { libc::kill(pid as libc::pid_t, sig.number()) };
+            if ret != 0 {
+                return Err(os_error(pending, io::Error::last_os_error()));
+            }
+            Ok(format!("Sent {} to PID {}", sig.name(), pid))
+        }
+        ProcessAction::Renice(nice) => {
+            // SAFETY: setpriority(2) has no memory-safety preconditions
+            let ret =
+                unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, *nice) };
+            if ret != 0 {
+                return Err(os_error(pending, io::Error::last_os_error()));
+            }
+            Ok(format!("Reniced PID {} to {}", pid, nice))
+        }
+        ProcessAction::RestartUnit(unit) => {
+            run_systemctl("restart", unit)?;
+            Ok(format!("Restarted {}", unit))
+        }
+        ProcessAction::StopUnit(unit) => {
+            run_systemctl("stop", unit)?;
+            Ok(format!("Stopped {}", unit))
+        }
+    }
+}
+
+/// Turn an errno from kill/setpriority into a readable error
+fn os_error(pending: &PendingAction, err: io::Error) -> color_eyre::Report {
+    match err.raw_os_error() {
+        Some(libc::EACCES) if matches!(pending.action, ProcessAction::Renice(_)) => {
+            eyre!("Permission denied: lowering the nice value requires root (try sudo)")
+        }
+        Some(libc::EPERM) | Some(libc::EACCES) => eyre!(
+            "Permission denied: PID {} runs as {} (try sudo)",
+            pending.pid,
+            pending.user
+        ),
+        Some(libc::ESRCH) => eyre!("PID {} no longer exists", pending.pid),
+        _ => eyre!("{} failed: {}", pending.action.describe(), err),
+    }
+}
+
+/// Run `systemctl <verb> <unit>` without interactive authentication
+fn run_systemctl(verb: &str, unit: &str) -> Result<()> {
+    let output = Command::new(SYSTEMCTL)
+        .args(["--no-ask-password", verb, unit])
+        .output()
+        .map_err(|e| eyre!("Failed to run systemctl: {}", e))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr.lines().next().unwrap_or("").trim();
+
+    if message.contains("Access denied") || message.contains("authentication required") {
+        return Err(eyre!(
+            "Permission denied: systemctl {} {} requires root (try sudo)",
+            verb,
+            unit
+        ));
+    }
+
+    Err(eyre!("systemctl {} {} failed: {}", verb, unit, message))
+}
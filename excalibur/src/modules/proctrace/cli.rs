@@ -0,0 +1,151 @@
+use super::export::{ExportFormat, SectionFilter, export_results, mask_secrets, sections};
+use super::parser::parse_query;
+use super::query::{NoMatch, QueryEngine, QueryResult};
+use super::supervisor::supervisor_label;
+use std::io::{ErrorKind, Write};
+
+/// Exit code when at least one process matched
+pub const EXIT_MATCH: i32 = 0;
+/// Exit code when nothing matched (like grep)
+pub const EXIT_NO_MATCH: i32 = 1;
+/// Exit code for an invalid query or a failed query/export
+pub const EXIT_ERROR: i32 = 2;
+
+/// Run a query without the TUI and print the results (`xcl pt <query>`)
+///
+/// Without a format a compact table is printed, followed by the sections
+/// selected in `filter`. Needs no TTY, so it works in scripts and over SSH.
+/// Returns the process exit code.
+pub fn run_query(query: &str, format: Option<ExportFormat>, filter: SectionFilter) -> i32 {
+    let query = match parse_query(query) {
+        Ok(q) => q,
+        Err(e) => {
+            eprintln!("Invalid query: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    let mut engine = QueryEngine::new();
+    // CPU filters take their own baseline of every process while evaluating
+    let cpu_sampled = query.filters_cpu();
+
+    // Fast paths report "nothing found" as an error with a hint
+    let mut results = match engine.execute(query) {
+        Ok(results) => {
+            if results.is_empty() {
+                eprintln!("No processes found");
+            }
+            results
+        }
+        Err(e) if e.downcast_ref::<NoMatch>().is_some() => {
+            eprintln!("{}", e);
+            Vec::new()
+        }
+        Err(e) => {
+            eprintln!("Query error: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    // One-shot runs have no previous CPU sample; every output format shows CPU%
+    if !cpu_sampled {
+        engine.measure_cpu(&mut results);
+    }
+
+    let output = match format {
+        Some(format) => export_results(&results, format, filter),
+        None => Ok(table(&results, filter)),
+    };
+    let text = match output {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Export failed: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    // A closed pipe (`| head`) is not an error for the caller
+    let mut stdout = std::io::stdout().lock();
+    match stdout
+        .write_all(text.as_bytes())
+        .and_then(|()| stdout.flush())
+    {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => {}
+        Err(e) => {
+            eprintln!("Write failed: {}", e);
+            return EXIT_ERROR;
+        }
+    }
+
+    if results.is_empty() {
+        EXIT_NO_MATCH
+    } else {
+        EXIT_MATCH
+    }
+}
+
+/// Compact table: one row per process, then the selected sections
+fn table(results: &[QueryResult], filter: SectionFilter) -> String {
+    if results.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!(
+        "{:>7}  {:<10} {:>6} {:>10} {:>10} {} {:<16} {:<28} {}\n",
+        "PID", "USER", "CPU%", "RSS", "UPTIME", "S", "NAME", "SUPERVISOR", "WARNINGS"
+    );
+    for result in results {
+        let p = &result.process;
+        let warnings: Vec<&str> = p.warnings.iter().map(|w| w.kind()).collect();
+        out.push_str(&format!(
+            "{:>7}  {:<10} {:>6.1} {:>10} {:>10} {} {:<16} {:<28} {}\n",
+            p.pid,
+            p.user,
+            p.cpu_percent,
+            p.memory_str(),
+            p.uptime_str(),
+            p.state,
+            p.name,
+            supervisor_label(&p.supervisor),
+            warnings.join(",")
+        ));
+    }
+
+    for result in results {
+        let p = &result.process;
+
+        if filter.tree {
+            out.push_str(&format!("\n{} (PID {}) process tree:\n", p.name, p.pid));
+            for (depth, ancestor) in result.ancestor_chain.iter().enumerate() {
+                let guide = if depth == 0 {
+                    String::new()
+                } else {
+                    format!("{}└─ ", "   ".repeat(depth - 1))
+                };
+                out.push_str(&format!(
+                    "  {}{} ({}, {})\n",
+                    guide, ancestor.name, ancestor.pid, ancestor.user
+                ));
+            }
+        }
+
+        let masked = mask_secrets(result);
+        for section in sections(&masked)
+            .into_iter()
+            .filter(|s| filter.selects(s.title) && s.title != "Ancestor chain")
+        {
+            out.push_str(&format!(
+                "\n{} (PID {}) {}:\n",
+                p.name,
+                p.pid,
+                section.title.to_lowercase()
+            ));
+            for line in section.lines {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+
+    out
+}
@@ -1,25 +1,60 @@
+use super::files::find_stale_files;
+use super::memory::{HIGH_OOM_SCORE, MemoryDetail};
+use super::network::{NetworkBinding, is_listening};
+use super::supervisor::{Supervisor, detect_supervisor};
+use super::users::{IdSet, user_name};
 use color_eyre::Result;
-use procfs::process::{Process, all_processes};
-use std::collections::HashMap;
+use procfs::process::{Process, Stat, all_processes};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Supervisor type for a process
-#[derive(Debug, Clone, PartialEq)]
-pub enum Supervisor {
-    Systemd { unit: String },
-    Docker { container_id: String },
-    Shell,
-    Unknown,
-}
-
 /// Warning types for processes
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ProcessWarning {
     RunningAsRoot,
-    HighCpu { percent: f32 },
-    HighMemory { gb: f64 },
-    LongUptime { days: u64 },
-    PublicBinding { port: u16, protocol: String },
+    HighCpu {
+        percent: f32,
+    },
+    HighMemory {
+        gb: f64,
+    },
+    LongUptime {
+        days: u64,
+    },
+    PublicBinding {
+        port: u16,
+        protocol: String,
+    },
+    Setuid {
+        real: String,
+        effective: String,
+    },
+    StaleBinary {
+        files: Vec<String>,
+    },
+    NearMemoryLimit {
+        percent: f64,
+        limit: u64,
+    },
+    HighOomScore {
+        score: u32,
+    },
+    Zombie {
+        parent: String,
+        ppid: u32,
+    },
+    Uninterruptible {
+        wchan: Option<String>,
+    },
+    Stopped {
+        traced: bool,
+    },
+    Orphaned {
+        subreaper: String,
+        subreaper_pid: u32,
+    },
 }
 
 impl ProcessWarning {
@@ -31,6 +66,33 @@ impl ProcessWarning {
             ProcessWarning::HighMemory { .. } => "⚠ HIGH_MEM",
             ProcessWarning::LongUptime { .. } => "⚠ LONG_UPTIME",
             ProcessWarning::PublicBinding { .. } => "⚠ PUBLIC",
+            ProcessWarning::Setuid { .. } => "⚠ SETUID",
+            ProcessWarning::StaleBinary { .. } => "⚠ STALE",
+            ProcessWarning::NearMemoryLimit { .. } => "⚠ MEM_LIMIT",
+            ProcessWarning::HighOomScore { .. } => "⚠ OOM",
+            ProcessWarning::Zombie { .. } => "⚠ ZOMBIE",
+            ProcessWarning::Uninterruptible { .. } => "⚠ D_STATE",
+            ProcessWarning::Stopped { .. } => "⚠ STOPPED",
+            ProcessWarning::Orphaned { .. } => "⚠ ORPHAN",
+        }
+    }
+
+    /// Get warning kind, used by `warn:` queries
+    pub fn kind(&self) -> &str {
+        match self {
+            ProcessWarning::RunningAsRoot => "root",
+            ProcessWarning::HighCpu { .. } => "high_cpu",
+            ProcessWarning::HighMemory { .. } => "high_mem",
+            ProcessWarning::LongUptime { .. } => "long_uptime",
+            ProcessWarning::PublicBinding { .. } => "public",
+            ProcessWarning::Setuid { .. } => "setuid",
+            ProcessWarning::StaleBinary { .. } => "stale_binary",
+            ProcessWarning::NearMemoryLimit { .. } => "mem_limit",
+            ProcessWarning::HighOomScore { .. } => "oom",
+            ProcessWarning::Zombie { .. } => "zombie",
+            ProcessWarning::Uninterruptible { .. } => "d_state",
+            ProcessWarning::Stopped { .. } => "stopped",
+            ProcessWarning::Orphaned { .. } => "orphan",
         }
     }
 
@@ -44,6 +106,44 @@ impl ProcessWarning {
             ProcessWarning::PublicBinding { port, protocol } => {
                 format!("Public binding: {}:{}", protocol, port)
             }
+            ProcessWarning::Setuid { real, effective } => {
+                format!("Setuid: started by {}, running as {}", real, effective)
+            }
+            ProcessWarning::StaleBinary { files } => {
+                let shown: Vec<&str> = files.iter().take(3).map(String::as_str).collect();
+                let more = files.len().saturating_sub(shown.len());
+                if more > 0 {
+                    format!("Needs restart: {} (+{} more)", shown.join(", "), more)
+                } else {
+                    format!("Needs restart: {}", shown.join(", "))
+                }
+            }
+            ProcessWarning::NearMemoryLimit { percent, limit } => format!(
+                "Near cgroup memory limit: {:.0}% of {}",
+                percent,
+                format_bytes(*limit)
+            ),
+            ProcessWarning::HighOomScore { score } => {
+                format!("High OOM score: {} (first to be killed)", score)
+            }
+            ProcessWarning::Zombie { parent, ppid } => {
+                format!("Zombie: not reaped by parent {} (PID {})", parent, ppid)
+            }
+            ProcessWarning::Uninterruptible { wchan } => match wchan {
+                Some(wchan) => format!("Uninterruptible sleep (D) in {}", wchan),
+                None => "Uninterruptible sleep (D)".to_string(),
+            },
+            ProcessWarning::Stopped { traced: true } => "Stopped by a tracer/debugger".to_string(),
+            ProcessWarning::Stopped { traced: false } => {
+                "Stopped (SIGSTOP/SIGTSTP), resume with SIGCONT".to_string()
+            }
+            ProcessWarning::Orphaned {
+                subreaper,
+                subreaper_pid,
+            } => format!(
+                "Orphaned: reparented to subreaper {} (PID {})",
+                subreaper, subreaper_pid
+            ),
         }
     }
 
@@ -56,21 +156,35 @@ impl ProcessWarning {
             ProcessWarning::HighMemory { .. } => Color::Yellow,
             ProcessWarning::LongUptime { .. } => Color::Cyan,
             ProcessWarning::PublicBinding { .. } => Color::Red,
+            ProcessWarning::Setuid { .. } => Color::Magenta,
+            ProcessWarning::StaleBinary { .. } => Color::Yellow,
+            ProcessWarning::NearMemoryLimit { .. } => Color::Red,
+            ProcessWarning::HighOomScore { .. } => Color::Red,
+            ProcessWarning::Zombie { .. } => Color::Magenta,
+            ProcessWarning::Uninterruptible { .. } => Color::Red,
+            ProcessWarning::Stopped { .. } => Color::Yellow,
+            ProcessWarning::Orphaned { .. } => Color::Cyan,
         }
     }
 }
 
 /// Process information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub ppid: u32,
     pub name: String,
     pub cmdline: Vec<String>,
-    pub user: String,
+    pub state: char,  // R, S, D, Z, T, ... (see proc(5))
+    pub session: u32, // session ID (SID)
+    pub user: String, // effective user name
+    pub uids: IdSet,
+    pub gids: IdSet,
     pub cpu_percent: f32,
     pub memory_rss: u64, // bytes
     pub start_time: u64, // timestamp (seconds since epoch)
+    #[serde(skip)]
+    pub start_ticks: u64, // clock ticks since boot, identifies the process across PID reuse
     pub supervisor: Supervisor,
     pub warnings: Vec<ProcessWarning>,
 }
@@ -78,26 +192,21 @@ pub struct ProcessInfo {
 impl ProcessInfo {
     /// Get formatted memory string (e.g., "45.2 MB")
     pub fn memory_str(&self) -> String {
-        let kb = self.memory_rss / 1024;
-        if kb < 1024 {
-            format!("{} KB", kb)
-        } else {
-            let mb = kb as f64 / 1024.0;
-            if mb < 1024.0 {
-                format!("{:.1} MB", mb)
-            } else {
-                format!("{:.1} GB", mb / 1024.0)
-            }
-        }
+        format_bytes(self.memory_rss)
     }
 
-    /// Get uptime duration in human-readable format
-    pub fn uptime_str(&self) -> String {
+    /// Get uptime in seconds
+    pub fn uptime_secs(&self) -> u64 {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        let uptime_secs = now.saturating_sub(self.start_time);
+        now.saturating_sub(self.start_time)
+    }
+
+    /// Get uptime duration in human-readable format
+    pub fn uptime_str(&self) -> String {
+        let uptime_secs = self.uptime_secs();
 
         let days = uptime_secs / 86400;
         let hours = (uptime_secs % 86400) / 3600;
@@ -113,6 +222,21 @@ impl ProcessInfo {
     }
 }
 
+/// Format a byte count as a human-readable string (e.g., "45.2 MB")
+pub fn format_bytes(bytes: u64) -> String {
+    let kb = bytes / 1024;
+    if kb < 1024 {
+        format!("{} KB", kb)
+    } else {
+        let mb = kb as f64 / 1024.0;
+        if mb < 1024.0 {
+            format!("{:.1} MB", mb)
+        } else {
+            format!("{:.1} GB", mb / 1024.0)
+        }
+    }
+}
+
 /// CPU stats for calculating percentage
 #[derive(Debug, Clone)]
 struct CpuStats {
@@ -125,12 +249,22 @@ struct CpuStats {
 #[derive(Debug)]
 pub struct ProcessCollector {
     last_cpu_stats: HashMap<u32, CpuStats>,
+    supervisors: HashMap<u32, SupervisorEntry>,
+}
+
+/// Supervisor detected for a process, valid while start time and parent match
+#[derive(Debug)]
+struct SupervisorEntry {
+    start_ticks: u64,
+    ppid: u32, // reparenting changes the Init/Shell fallback
+    supervisor: Supervisor,
 }
 
 impl ProcessCollector {
     pub fn new() -> Self {
         Self {
             last_cpu_stats: HashMap::new(),
+            supervisors: HashMap::new(),
         }
     }
 
@@ -147,68 +281,69 @@ impl ProcessCollector {
             }
         }
 
+        // Drop CPU stats of processes that have exited
+        let alive: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        self.last_cpu_stats.retain(|pid, _| alive.contains(pid));
+        self.supervisors.retain(|pid, _| alive.contains(pid));
+
         Ok(processes)
     }
 
+    /// Check whether CPU samples from a previous collection exist
+    pub fn has_samples(&self) -> bool {
+        !self.last_cpu_stats.is_empty()
+    }
+
+    /// Read a single process, tracking CPU usage across calls
+    ///
+    /// Unlike [`read_process`], repeated calls report real CPU% deltas.
+    pub fn read(&mut self, pid: u32) -> Result<ProcessInfo> {
+        let process = Process::new(pid as i32)?;
+        self.collect_process_info(&process)
+    }
+
     /// Collect information for a single process
     fn collect_process_info(&mut self, process: &Process) -> Result<ProcessInfo> {
         let pid = process.pid as u32;
         let stat = process.stat()?;
-        let status = process.status().ok();
-
-        // Get process name
-        let name = stat.comm.clone();
 
-        // Get parent PID
-        let ppid = stat.ppid as u32;
-
-        // Get command line
-        let cmdline = process
-            .cmdline()
-            .unwrap_or_default()
-            .into_iter()
-            .collect::<Vec<String>>();
-
-        // Get user (UID)
-        let user = if let Some(ref s) = status {
-            s.ruid.to_string()
-        } else {
-            "?".to_string()
-        };
-
-        // Get memory (RSS in pages, convert to bytes)
-        let page_size = procfs::page_size();
-        let memory_rss = stat.rss * page_size;
+        let cpu_percent = self.calculate_cpu_percent(pid, stat.utime, stat.stime);
+        let supervisor = self.supervisor(pid, stat.starttime, stat.ppid as u32);
 
-        // Get start time (in clock ticks since boot, need to convert)
-        let boot_time = procfs::boot_time_secs().unwrap_or(0);
-        let ticks_per_second = procfs::ticks_per_second();
-        let start_time = boot_time + (stat.starttime / ticks_per_second);
+        Ok(build_process_info(process, &stat, cpu_percent, supervisor))
+    }
 
-        // Calculate CPU percentage
-        let cpu_percent = self.calculate_cpu_percent(pid, stat.utime, stat.stime);
+    /// Detect the supervisor of a process, reusing the result of earlier collections
+    ///
+    /// Detection reads cgroups, walks ancestors and reads environments, so it
+    /// only runs again when the PID was reused or the process was reparented.
+    fn supervisor(&mut self, pid: u32, start_ticks: u64, ppid: u32) -> Supervisor {
+        if let Some(entry) = self.supervisors.get(&pid)
+            && entry.start_ticks == start_ticks
+            && entry.ppid == ppid
+        {
+            return entry.supervisor.clone();
+        }
 
-        // Detect supervisor
         let supervisor = detect_supervisor(pid);
-
-        // Create info struct
-        let mut info = ProcessInfo {
-            pid,
-            ppid,
-            name,
-            cmdline,
-            user,
-            cpu_percent,
-            memory_rss,
-            start_time,
-            supervisor,
-            warnings: Vec::new(),
+        // Runtime state files may not be written yet when a container starts
+        let resolved = match &supervisor {
+            Supervisor::Container(container) | Supervisor::Kubernetes { container, .. } => {
+                container.name.is_some()
+            }
+            _ => true,
         };
-
-        // Detect warnings
-        info.warnings = detect_warnings(&info);
-
-        Ok(info)
+        if resolved {
+            self.supervisors.insert(
+                pid,
+                SupervisorEntry {
+                    start_ticks,
+                    ppid,
+                    supervisor: supervisor.clone(),
+                },
+            );
+        }
+        supervisor
     }
 
     /// Calculate CPU percentage based on delta
@@ -251,72 +386,52 @@ impl ProcessCollector {
     }
 }
 
-/// Detect supervisor for a process
-pub fn detect_supervisor(pid: u32) -> Supervisor {
-    // Read cgroup file
-    if let Ok(cgroup_content) = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)) {
-        // Check for systemd unit
-        for line in cgroup_content.lines() {
-            if line.contains(".service") {
-                // Extract unit name
-                // Format: 12:pids:/system.slice/nginx.service
-                if let Some(unit_part) = line.split('/').last() {
-                    return Supervisor::Systemd {
-                        unit: unit_part.to_string(),
-                    };
-                }
-            }
-
-            // Check for Docker container
-            if line.contains("/docker/") {
-                // Extract container ID
-                // Format: 11:cpuset:/docker/abc123def456...
-                if let Some(container_part) = line.split("/docker/").nth(1) {
-                    let container_id = container_part.trim_end_matches('\n').to_string();
-                    let short_id = if container_id.len() > 12 {
-                        &container_id[..12]
-                    } else {
-                        &container_id
-                    };
-                    return Supervisor::Docker {
-                        container_id: short_id.to_string(),
-                    };
-                }
-            }
-        }
-    }
-
-    // Fallback: check if parent is systemd
-    if pid == 1 {
-        return Supervisor::Unknown;
-    }
-
-    // Check parent process
-    if let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
-        // Parse PPID from stat
-        if let Some(ppid_str) = stat.split_whitespace().nth(3) {
-            if let Ok(ppid) = ppid_str.parse::<u32>() {
-                if ppid == 1 {
-                    return Supervisor::Systemd {
-                        unit: "direct".to_string(),
-                    };
-                }
-            }
-        }
-    }
+/// Extract real/effective/saved/filesystem UIDs and GIDs from a status record
+fn read_ids(status: Option<&procfs::process::Status>) -> (IdSet, IdSet) {
+    // Unknown IDs must not look like root
+    let Some(s) = status else {
+        let unknown = IdSet {
+            real: u32::MAX,
+            effective: u32::MAX,
+            saved: u32::MAX,
+            filesystem: u32::MAX,
+        };
+        return (unknown, unknown);
+    };
 
-    Supervisor::Shell
+    (
+        IdSet {
+            real: s.ruid,
+            effective: s.euid,
+            saved: s.suid,
+            filesystem: s.fuid,
+        },
+        IdSet {
+            real: s.rgid,
+            effective: s.egid,
+            saved: s.sgid,
+            filesystem: s.fgid,
+        },
+    )
 }
 
 /// Detect warnings for a process
 fn detect_warnings(info: &ProcessInfo) -> Vec<ProcessWarning> {
     let mut warnings = Vec::new();
 
-    // Check root privileges (UID 0)
-    if info.user == "0" {
+    // Check root privileges (effective UID 0)
+    if info.uids.effective == 0 {
         warnings.push(ProcessWarning::RunningAsRoot);
     }
 
+    // Check setuid execution (effective UID differs from real UID)
+    if info.uids.effective != info.uids.real {
+        warnings.push(ProcessWarning::Setuid {
+            real: user_name(info.uids.real),
+            effective: user_name(info.uids.effective),
+        });
+    }
+
     // Check high CPU (> 80%)
     if info.cpu_percent > 80.0 {
         warnings.push(ProcessWarning::HighCpu {
@@ -330,6 +445,8 @@ fn detect_warnings(info: &ProcessInfo) -> Vec<ProcessWarning> {
         warnings.push(ProcessWarning::HighMemory { gb: memory_gb });
     }
 
+    warnings.extend(detect_state_warnings(info));
+
     // Check long uptime (> 90 days)
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -343,57 +460,151 @@ fn detect_warnings(info: &ProcessInfo) -> Vec<ProcessWarning> {
     warnings
 }
 
+/// Scheduler state warnings: zombies, D-state, stopped and orphaned processes
+fn detect_state_warnings(info: &ProcessInfo) -> Vec<ProcessWarning> {
+    let mut warnings = Vec::new();
+    let parent = || Process::new(info.ppid as i32).and_then(|p| p.stat()).ok();
+
+    match info.state {
+        'Z' => warnings.push(ProcessWarning::Zombie {
+            parent: parent().map_or_else(|| "?".to_string(), |p| p.comm),
+            ppid: info.ppid,
+        }),
+        'D' => warnings.push(ProcessWarning::Uninterruptible {
+            wchan: std::fs::read_to_string(format!("/proc/{}/wchan", info.pid))
+                .ok()
+                .map(|w| w.trim().to_string())
+                .filter(|w| !w.is_empty() && w != "0"),
+        }),
+        'T' => warnings.push(ProcessWarning::Stopped { traced: false }),
+        't' => warnings.push(ProcessWarning::Stopped { traced: true }),
+        _ => {}
+    }
+
+    // A child shares its parent's session unless it called setsid() (and then
+    // leads its own). A non-leader whose session leader is gone and whose
+    // parent sits in another session was reparented; with a parent other than
+    // PID 1 (or 0 for kernel threads) that parent is a child subreaper.
+    if info.ppid > 1
+        && info.session != 0
+        && info.session != info.pid
+        && !std::path::Path::new(&format!("/proc/{}", info.session)).exists()
+        && let Some(parent) = parent()
+        && parent.session as u32 != info.session
+    {
+        warnings.push(ProcessWarning::Orphaned {
+            subreaper: parent.comm,
+            subreaper_pid: info.ppid,
+        });
+    }
+
+    warnings
+}
+
+/// Detect warnings that depend on a process's network bindings
+///
+/// Flags TCP listeners and unconnected UDP sockets bound to a wildcard
+/// address (0.0.0.0 / ::), i.e. reachable from outside the box. Loopback and
+/// specific-interface bindings are not flagged.
+pub fn detect_binding_warnings(bindings: &[NetworkBinding]) -> Vec<ProcessWarning> {
+    let mut warnings: Vec<ProcessWarning> = Vec::new();
+
+    for binding in bindings.iter().filter(|b| is_public_listener(b)) {
+        let warning = ProcessWarning::PublicBinding {
+            port: binding.local_port,
+            protocol: binding.protocol.as_str().to_string(),
+        };
+        // IPv4 and IPv6 wildcard sockets often share a port
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    warnings
+}
+
+/// Detect deleted or replaced executables and libraries still in use
+pub fn detect_stale_warning(pid: u32) -> Option<ProcessWarning> {
+    let files = find_stale_files(pid);
+    (!files.is_empty()).then_some(ProcessWarning::StaleBinary { files })
+}
+
+/// Detect processes close to their cgroup memory limit or likely OOM victims
+pub fn detect_memory_warnings(memory: &MemoryDetail) -> Vec<ProcessWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some(ref cgroup) = memory.cgroup
+        && cgroup.is_near_limit()
+        && let (Some(ratio), Some(limit)) = (cgroup.usage_ratio(), cgroup.limit)
+    {
+        warnings.push(ProcessWarning::NearMemoryLimit {
+            percent: ratio * 100.0,
+            limit,
+        });
+    }
+
+    if let Some(score) = memory.oom_score.filter(|&s| s >= HIGH_OOM_SCORE) {
+        warnings.push(ProcessWarning::HighOomScore { score });
+    }
+
+    warnings
+}
+
+/// Check whether a binding accepts traffic on all interfaces
+pub fn is_public_listener(binding: &NetworkBinding) -> bool {
+    binding.local_addr.is_unspecified() && is_listening(binding)
+}
+
 /// Read single process by PID (for query mode)
 pub fn read_process(pid: u32) -> Result<ProcessInfo> {
     let process = Process::new(pid as i32)?;
     let stat = process.stat()?;
-    let status = process.status().ok();
 
-    // Get process name
-    let name = stat.comm.clone();
+    // No CPU calculation for single-process reads
+    Ok(build_process_info(
+        &process,
+        &stat,
+        0.0,
+        detect_supervisor(pid),
+    ))
+}
 
-    // Get parent PID
-    let ppid = stat.ppid as u32;
+/// Build process info from /proc, with CPU% and supervisor supplied by the caller
+fn build_process_info(
+    process: &Process,
+    stat: &Stat,
+    cpu_percent: f32,
+    supervisor: Supervisor,
+) -> ProcessInfo {
+    let status = process.status().ok();
 
     // Get command line
-    let cmdline = process
-        .cmdline()
-        .unwrap_or_default()
-        .into_iter()
-        .collect::<Vec<String>>();
-
-    // Get user (UID)
-    let user = if let Some(ref s) = status {
-        s.ruid.to_string()
-    } else {
-        "?".to_string()
+    let cmdline = process.cmdline().unwrap_or_default();
+
+    // Get user and group IDs
+    let (uids, gids) = read_ids(status.as_ref());
+    let user = match status {
+        Some(_) => user_name(uids.effective),
+        None => "?".to_string(),
     };
 
     // Get memory (RSS in pages, convert to bytes)
-    let page_size = procfs::page_size();
-    let memory_rss = stat.rss * page_size;
-
-    // Get start time
-    let boot_time = procfs::boot_time_secs().unwrap_or(0);
-    let ticks_per_second = procfs::ticks_per_second();
-    let start_time = boot_time + (stat.starttime / ticks_per_second);
-
-    // Detect supervisor
-    let supervisor = detect_supervisor(pid);
+    let memory_rss = stat.rss * procfs::page_size();
 
-    // No CPU calculation for single-process reads
-    let cpu_percent = 0.0;
-
-    // Create process info
     let mut info = ProcessInfo {
-        pid,
-        ppid,
-        name,
+        pid: process.pid as u32,
+        ppid: stat.ppid as u32,
+        name: stat.comm.clone(),
         cmdline,
+        state: stat.state,
+        session: stat.session as u32,
         user,
+        uids,
+        gids,
         cpu_percent,
         memory_rss,
-        start_time,
+        start_time: start_time_secs(stat.starttime),
+        start_ticks: stat.starttime,
         supervisor,
         warnings: Vec::new(),
     };
@@ -401,7 +612,22 @@ pub fn read_process(pid: u32) -> Result<ProcessInfo> {
     // Detect warnings
     info.warnings = detect_warnings(&info);
 
-    Ok(info)
+    info
+}
+
+/// Convert a stat starttime (clock ticks since boot) to seconds since epoch
+fn start_time_secs(starttime: u64) -> u64 {
+    let boot_time = procfs::boot_time_secs().unwrap_or(0);
+    boot_time + (starttime / procfs::ticks_per_second())
+}
+
+/// Re-read the start time of a PID in clock ticks (see [`ProcessInfo::start_ticks`])
+///
+/// Used to detect PID reuse: a different start time means a different process.
+/// Ticks rather than seconds, so a PID reused within the same second is caught.
+pub fn read_start_ticks(pid: u32) -> Option<u64> {
+    let stat = Process::new(pid as i32).ok()?.stat().ok()?;
+    Some(stat.starttime)
 }
 
 /// Read working directory from /proc/[pid]/cwd
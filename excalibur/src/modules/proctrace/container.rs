@@ -0,0 +1,432 @@
+use super::users::home_dir;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Container runtime a process was started by
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+}
+
+impl ContainerRuntime {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::CriO => "cri-o",
+        }
+    }
+}
+
+/// Container a process runs in, with metadata from the runtime's state files
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContainerInfo {
+    pub runtime: ContainerRuntime,
+    pub id: String, // full 64-char ID
+    pub name: Option<String>,
+    pub image: Option<String>,
+}
+
+impl ContainerInfo {
+    /// Get the short (12-char) container ID, as shown by `docker ps`
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+
+    /// Get the container name, falling back to the short ID
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_else(|| self.short_id())
+    }
+}
+
+/// Kubernetes pod a container belongs to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PodInfo {
+    pub uid: Option<String>,
+    pub name: Option<String>,
+    pub namespace: Option<String>,
+}
+
+/// Container found in a cgroup path, before metadata resolution
+struct CgroupContainer {
+    runtime: Option<ContainerRuntime>, // None: bare ID under kubepods (cgroupfs driver)
+    id: String,
+    pod_uid: Option<String>,
+}
+
+/// Metadata read from runtime state files
+#[derive(Debug, Clone, Default)]
+struct RuntimeMetadata {
+    runtime: Option<ContainerRuntime>,
+    name: Option<String>,
+    image: Option<String>,
+    pod_name: Option<String>,
+    pod_namespace: Option<String>,
+}
+
+/// Successful metadata lookups per container ID, with the time they were read
+static METADATA_CACHE: OnceLock<Mutex<HashMap<String, (Instant, RuntimeMetadata)>>> =
+    OnceLock::new();
+
+/// How long a cached lookup stays valid outside of query refreshes
+const METADATA_TTL: Duration = Duration::from_secs(30);
+
+/// containerd keeps one directory per namespace (k8s.io, moby, ...) under here
+const CONTAINERD_TASK_DIR: &str = "/run/containerd/io.containerd.runtime.v2.task";
+
+/// CRI-O / podman (rootful) container storage
+const CONTAINERS_STORAGE_DIRS: [&str; 2] = [
+    "/run/containers/storage/overlay-containers",
+    "/var/lib/containers/storage/overlay-containers",
+];
+
+fn is_container_id(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parse a cgroup path for a container
+///
+/// Handles cgroup v1 and v2 layouts of the common runtimes:
+///   /docker/<id>                                   (docker, cgroupfs driver)
+///   /system.slice/docker-<id>.scope                (docker, systemd driver)
+///   /machine.slice/libpod-<id>.scope               (podman)
+///   /kubepods.slice/.../kubepods-besteffort-pod<uid>.slice/cri-containerd-<id>.scope
+///   /kubepods.slice/.../crio-<id>.scope
+///   /kubepods/burstable/pod<uid>/<id>              (kubelet, cgroupfs driver)
+fn parse_cgroup_path(path: &str) -> Option<CgroupContainer> {
+    let mut pod_uid = None;
+    let mut found: Option<(Option<ContainerRuntime>, String)> = None;
+    let mut previous = "";
+
+    for segment in path.split('/') {
+        let unit = segment.strip_suffix(".scope").unwrap_or(segment);
+
+        if let Some(idx) = segment.rfind("pod")
+            && (segment.starts_with("pod") || segment.starts_with("kubepods-"))
+        {
+            // systemd driver escapes '-' in the pod UID as '_'
+            let uid = segment[idx + 3..]
+                .trim_end_matches(".slice")
+                .replace('_', "-");
+            if uid.len() >= 32 {
+                pod_uid = Some(uid);
+            }
+        }
+
+        // conmon monitors live next to the container but are not part of it
+        let candidate = if unit.contains("-conmon-") {
+            None
+        } else if let Some(id) = unit.strip_prefix("docker-") {
+            Some((Some(ContainerRuntime::Docker), id))
+        } else if let Some(id) = unit.strip_prefix("libpod-") {
+            Some((Some(ContainerRuntime::Podman), id))
+        } else if let Some(id) = unit.strip_prefix("cri-containerd-") {
+            Some((Some(ContainerRuntime::Containerd), id))
+        } else if let Some(id) = unit.strip_prefix("crio-") {
+            Some((Some(ContainerRuntime::CriO), id))
+        } else if previous == "docker" {
+            Some((Some(ContainerRuntime::Docker), unit))
+        } else if pod_uid.is_some() {
+            Some((None, unit))
+        } else {
+            None
+        };
+
+        if let Some((runtime, id)) = candidate
+            && is_container_id(id)
+        {
+            found = Some((runtime, id.to_string()));
+        }
+
+        previous = segment;
+    }
+
+    let (runtime, id) = found?;
+    Some(CgroupContainer {
+        runtime,
+        id,
+        pod_uid,
+    })
+}
+
+fn read_json(path: impl AsRef<std::path::Path>) -> Option<Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn json_str(value: &Value, pointer: &str) -> Option<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Docker: /var/lib/docker/containers/<id>/config.v2.json
+fn read_docker_metadata(id: &str) -> Option<RuntimeMetadata> {
+    let config = read_json(format!("/var/lib/docker/containers/{}/config.v2.json", id))?;
+    Some(RuntimeMetadata {
+        runtime: Some(ContainerRuntime::Docker),
+        name: json_str(&config, "/Name").map(|n| n.trim_start_matches('/').to_string()),
+        image: json_str(&config, "/Config/Image"),
+        // dockershim-era pods carry their identity in labels
+        pod_name: json_str(&config, "/Config/Labels/io.kubernetes.pod.name"),
+        pod_namespace: json_str(&config, "/Config/Labels/io.kubernetes.pod.namespace"),
+    })
+}
+
+/// Podman: containers.json in rootful storage, or in the rootless storage of
+/// the container owner (`uid`)
+fn read_podman_metadata(id: &str, uid: u32) -> Option<RuntimeMetadata> {
+    let mut indexes: Vec<PathBuf> = CONTAINERS_STORAGE_DIRS
+        .iter()
+        .map(|dir| PathBuf::from(dir).join("containers.json"))
+        .collect();
+    if uid != 0
+        && let Some(home) = home_dir(uid)
+    {
+        indexes
+            .push(home.join(".local/share/containers/storage/overlay-containers/containers.json"));
+    }
+
+    for index in indexes {
+        let Some(Value::Array(entries)) = read_json(&index) else {
+            continue;
+        };
+        let Some(entry) = entries.iter().find(|e| e["id"].as_str() == Some(id)) else {
+            continue;
+        };
+
+        // "metadata" is itself a JSON document encoded as a string
+        let metadata: Option<Value> = entry["metadata"]
+            .as_str()
+            .and_then(|m| serde_json::from_str(m).ok());
+
+        return Some(RuntimeMetadata {
+            runtime: Some(ContainerRuntime::Podman),
+            name: json_str(entry, "/names/0"),
+            image: metadata.and_then(|m| json_str(&m, "/image-name")),
+            ..Default::default()
+        });
+    }
+
+    None
+}
+
+/// Read an OCI config.json annotation
+fn annotation(config: &Value, key: &str) -> Option<String> {
+    config
+        .get("annotations")
+        .and_then(|a| a.get(key))
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// containerd: OCI bundle of the task, in any namespace (k8s.io, moby, ...)
+fn read_containerd_metadata(id: &str) -> Option<RuntimeMetadata> {
+    let namespaces = std::fs::read_dir(CONTAINERD_TASK_DIR).ok()?;
+
+    for ns in namespaces.flatten() {
+        let Some(config) = read_json(ns.path().join(id).join("config.json")) else {
+            continue;
+        };
+        return Some(RuntimeMetadata {
+            runtime: Some(ContainerRuntime::Containerd),
+            name: annotation(&config, "io.kubernetes.cri.container-name"),
+            image: annotation(&config, "io.kubernetes.cri.image-name"),
+            pod_name: annotation(&config, "io.kubernetes.cri.sandbox-name"),
+            pod_namespace: annotation(&config, "io.kubernetes.cri.sandbox-namespace"),
+        });
+    }
+
+    None
+}
+
+/// CRI-O: userdata/config.json in containers storage
+fn read_crio_metadata(id: &str) -> Option<RuntimeMetadata> {
+    for dir in CONTAINERS_STORAGE_DIRS {
+        let Some(config) = read_json(format!("{}/{}/userdata/config.json", dir, id)) else {
+            continue;
+        };
+        return Some(RuntimeMetadata {
+            runtime: Some(ContainerRuntime::CriO),
+            name: annotation(&config, "io.kubernetes.container.name"),
+            image: annotation(&config, "io.kubernetes.cri-o.ImageName"),
+            pod_name: annotation(&config, "io.kubernetes.pod.name"),
+            pod_namespace: annotation(&config, "io.kubernetes.pod.namespace"),
+        });
+    }
+
+    None
+}
+
+/// Forget all cached metadata, so the next lookups see recreated containers
+pub fn clear_metadata_cache() {
+    if let Some(Ok(mut cache)) = METADATA_CACHE.get().map(Mutex::lock) {
+        cache.clear();
+    }
+}
+
+/// Look up container metadata, trying the runtime's own state first
+///
+/// Only successful lookups are cached: state files of a starting container
+/// may not exist yet.
+fn resolve_metadata(runtime: Option<ContainerRuntime>, id: &str, uid: u32) -> RuntimeMetadata {
+    let cache = METADATA_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((_, cached)) = cache
+        .lock()
+        .ok()
+        .and_then(|c| c.get(id).cloned())
+        .filter(|(read_at, _)| read_at.elapsed() < METADATA_TTL)
+    {
+        return cached;
+    }
+
+    let metadata = match runtime {
+        Some(ContainerRuntime::Docker) => read_docker_metadata(id),
+        Some(ContainerRuntime::Podman) => read_podman_metadata(id, uid),
+        Some(ContainerRuntime::Containerd) => read_containerd_metadata(id),
+        Some(ContainerRuntime::CriO) => read_crio_metadata(id),
+        // Bare ID under kubepods: any CRI runtime may own it
+        None => read_containerd_metadata(id)
+            .or_else(|| read_crio_metadata(id))
+            .or_else(|| read_docker_metadata(id)),
+    };
+
+    let Some(metadata) = metadata else {
+        return RuntimeMetadata::default();
+    };
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(id.to_string(), (Instant::now(), metadata.clone()));
+    }
+    metadata
+}
+
+/// Detect the container (and Kubernetes pod) of a process from its cgroup file
+///
+/// Returns the container and, when it belongs to a Kubernetes pod, the pod.
+/// `uid` is the process owner, whose storage holds rootless podman containers.
+pub fn detect_container(
+    cgroup_content: &str,
+    uid: u32,
+) -> Option<(ContainerInfo, Option<PodInfo>)> {
+    let found = cgroup_content
+        .lines()
+        // "hierarchy-ID:controllers:path" (v2: "0::path")
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(parse_cgroup_path)?;
+
+    let metadata = resolve_metadata(found.runtime, &found.id, uid);
+
+    let container = ContainerInfo {
+        // Runtime unknown from the path and no state found: kubelet defaults to containerd
+        runtime: metadata
+            .runtime
+            .or(found.runtime)
+            .unwrap_or(ContainerRuntime::Containerd),
+        id: found.id,
+        name: metadata.name,
+        image: metadata.image,
+    };
+
+    let pod = (found.pod_uid.is_some() || metadata.pod_name.is_some()).then_some(PodInfo {
+        uid: found.pod_uid,
+        name: metadata.pod_name,
+        namespace: metadata.pod_namespace,
+    });
+
+    Some((container, pod))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "3f4e1c0b9a8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f";
+
+    fn parse(path: &str) -> Option<(Option<ContainerRuntime>, String, Option<String>)> {
+        parse_cgroup_path(path).map(|c| (c.runtime, c.id, c.pod_uid))
+    }
+
+    #[test]
+    fn docker_paths() {
+        let expected = Some((Some(ContainerRuntime::Docker), ID.to_string(), None));
+        assert_eq!(parse(&format!("/docker/{}", ID)), expected);
+        assert_eq!(
+            parse(&format!("/system.slice/docker-{}.scope", ID)),
+            expected
+        );
+    }
+
+    #[test]
+    fn kubernetes_paths() {
+        let uid = "0b6c7a2e-4f1d-4c3b-9a8e-7d6c5b4a3f2e";
+        let expected = |runtime| Some((runtime, ID.to_string(), Some(uid.to_string())));
+
+        assert_eq!(
+            parse(&format!(
+                "/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod{}.slice/cri-containerd-{}.scope",
+                uid.replace('-', "_"),
+                ID
+            )),
+            expected(Some(ContainerRuntime::Containerd))
+        );
+        assert_eq!(
+            parse(&format!(
+                "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod{}.slice/crio-{}.scope",
+                uid.replace('-', "_"),
+                ID
+            )),
+            expected(Some(ContainerRuntime::CriO))
+        );
+        assert_eq!(
+            parse(&format!("/kubepods/burstable/pod{}/{}", uid, ID)),
+            expected(None)
+        );
+    }
+
+    #[test]
+    fn podman_paths() {
+        let expected = Some((Some(ContainerRuntime::Podman), ID.to_string(), None));
+        assert_eq!(
+            parse(&format!("/machine.slice/libpod-{}.scope", ID)),
+            expected
+        );
+        assert_eq!(
+            parse(&format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope",
+                ID
+            )),
+            expected
+        );
+        assert_eq!(
+            parse(&format!("/machine.slice/libpod-{}.scope/container", ID)),
+            expected
+        );
+        // conmon is the container's monitor, not part of it
+        assert_eq!(
+            parse(&format!("/machine.slice/libpod-conmon-{}.scope", ID)),
+            None
+        );
+    }
+
+    #[test]
+    fn lxc_and_host_paths_are_not_containers() {
+        // LXC has no state files to resolve; its processes are not reported as containers
+        assert_eq!(parse("/lxc.payload.web/init.scope"), None);
+        assert_eq!(parse("/lxc/web"), None);
+        assert_eq!(parse("/system.slice/sshd.service"), None);
+        assert_eq!(parse("/user.slice/user-1000.slice/session-3.scope"), None);
+        assert_eq!(parse("/docker/not-an-id"), None);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+/// Key fragments that mark a variable as secret (matched case-insensitively)
+const SECRET_MARKERS: [&str; 4] = ["TOKEN", "SECRET", "PASSWORD", "KEY"];
+
+/// Placeholder shown instead of a secret value
+pub const MASKED_VALUE: &str = "********";
+
+/// Check whether a variable name looks like it holds a secret
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Value as it should be displayed: masked for secret keys unless revealed
+pub fn display_value<'a>(key: &str, value: &'a str, reveal: bool) -> &'a str {
+    if !reveal && is_secret_key(key) {
+        MASKED_VALUE
+    } else {
+        value
+    }
+}
+
+/// Command-line argument with a secret `KEY=value` / `--key=value` masked
+///
+/// Covers the common ways secrets leak into argv (`PGPASSWORD=… psql`,
+/// `--api-token=…`); values passed as a separate argument are not detected.
+pub fn mask_argument(arg: &str) -> String {
+    match arg.split_once('=') {
+        Some((key, _)) if is_secret_key(key.trim_start_matches('-')) => {
+            format!("{}={}", key, MASKED_VALUE)
+        }
+        _ => arg.to_string(),
+    }
+}
+
+/// How a variable differs from the parent's environment
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvChange {
+    Added,
+    Changed { parent_value: String },
+}
+
+/// One variable that was added or changed relative to the parent
+#[derive(Debug, Clone)]
+pub struct EnvDiffEntry<'a> {
+    pub key: &'a str,
+    pub value: &'a str,
+    pub change: EnvChange,
+}
+
+/// Variables added or changed relative to the parent, sorted by key
+///
+/// Variables the parent has but the child dropped are not listed; the point
+/// is to see what a wrapper (script, supervisor, sudo) injected.
+pub fn diff_environment<'a>(
+    env: &'a HashMap<String, String>,
+    parent: &HashMap<String, String>,
+) -> Vec<EnvDiffEntry<'a>> {
+    let mut entries: Vec<EnvDiffEntry> = env
+        .iter()
+        .filter_map(|(key, value)| {
+            let change = match parent.get(key) {
+                None => EnvChange::Added,
+                Some(parent_value) if parent_value != value => EnvChange::Changed {
+                    parent_value: parent_value.clone(),
+                },
+                Some(_) => return None,
+            };
+            Some(EnvDiffEntry { key, value, change })
+        })
+        .collect();
+
+    entries.sort_by_key(|e| e.key);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_argument_hides_secret_assignments() {
+        assert_eq!(mask_argument("--api-token=abc123"), "--api-token=********");
+        assert_eq!(mask_argument("PGPASSWORD=hunter2"), "PGPASSWORD=********");
+        assert_eq!(mask_argument("--port=8080"), "--port=8080");
+        assert_eq!(mask_argument("--password"), "--password");
+        assert_eq!(mask_argument("manage.py"), "manage.py");
+    }
+
+    #[test]
+    fn secret_keys_are_detected() {
+        for key in [
+            "AWS_SECRET_ACCESS_KEY",
+            "GITHUB_TOKEN",
+            "DB_PASSWORD",
+            "SSH_PRIVATE_KEY",
+            "api_key",
+            "npm_config_//registry.npmjs.org/:_authToken",
+        ] {
+            assert!(is_secret_key(key), "{} should be secret", key);
+        }
+        for key in ["PATH", "HOME", "LANG", "USER", "SSH_CONNECTION"] {
+            assert!(!is_secret_key(key), "{} should not be secret", key);
+        }
+    }
+
+    #[test]
+    fn display_value_masks_unless_revealed() {
+        assert_eq!(display_value("GITHUB_TOKEN", "ghp_x", false), MASKED_VALUE);
+        assert_eq!(display_value("GITHUB_TOKEN", "ghp_x", true), "ghp_x");
+        assert_eq!(display_value("HOME", "/root", false), "/root");
+    }
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lists_added_and_changed_variables_sorted() {
+        let child = env(&[
+            ("PATH", "/opt/app/bin:/usr/bin"),
+            ("HOME", "/root"),
+            ("APP_ENV", "production"),
+            ("LANG", "C.UTF-8"),
+        ]);
+        let parent = env(&[
+            ("PATH", "/usr/bin"),
+            ("HOME", "/root"),
+            ("LANG", "C.UTF-8"),
+            ("TERM", "xterm"),
+        ]);
+
+        let diff: Vec<_> = diff_environment(&child, &parent)
+            .into_iter()
+            .map(|e| (e.key, e.value, e.change))
+            .collect();
+
+        // Unchanged (HOME, LANG) and dropped (TERM) variables are left out
+        assert_eq!(
+            diff,
+            [
+                ("APP_ENV", "production", EnvChange::Added),
+                (
+                    "PATH",
+                    "/opt/app/bin:/usr/bin",
+                    EnvChange::Changed {
+                        parent_value: "/usr/bin".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_of_identical_environments_is_empty() {
+        let vars = env(&[("PATH", "/usr/bin"), ("HOME", "/root")]);
+        assert!(diff_environment(&vars, &vars.clone()).is_empty());
+        assert!(diff_environment(&HashMap::new(), &vars).is_empty());
+    }
+}
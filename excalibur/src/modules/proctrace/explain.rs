@@ -0,0 +1,258 @@
+use super::collector::{ProcessInfo, is_public_listener};
+use super::network::{ConnectionState, format_socket_addr, is_listening};
+use super::query::QueryResult;
+use super::supervisor::Supervisor;
+use super::users::user_name;
+
+/// Build the "Why is this running?" narrative for a result
+///
+/// The first line is a single causal sentence (who started the process, how
+/// it is kept alive, what it exposes); the following lines add the parent
+/// chain and any warnings. Lines are plain text so they can be exported as-is.
+pub fn explain(result: &QueryResult) -> Vec<String> {
+    let process = &result.process;
+    let mut clauses = vec![format!(
+        "{} (PID {}) {}",
+        process.name,
+        process.pid,
+        origin_clause(result)
+    )];
+
+    clauses.extend(systemd_clauses(result));
+
+    if result.exited {
+        clauses.push("has since exited".to_string());
+    } else {
+        clauses.push(format!("running {}", process.uptime_str()));
+    }
+
+    clauses.extend(network_clauses(result));
+
+    let mut lines = vec![format!("{}.", clauses.join(", "))];
+
+    if let Some(chain) = parent_chain(&result.ancestor_chain) {
+        lines.push(format!("Parent chain: {}.", chain));
+    }
+
+    for warning in &process.warnings {
+        lines.push(format!("Note: {}.", warning.description()));
+    }
+
+    lines
+}
+
+/// Plain-text export of the narrative
+pub fn explain_text(result: &QueryResult) -> String {
+    let mut text = format!(
+        "Why is {} (PID {}) running?\n\n",
+        result.process.name, result.process.pid
+    );
+    for line in explain(result) {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text
+}
+
+/// Describe who started the process, from the supervisor data
+fn origin_clause(result: &QueryResult) -> String {
+    match &result.process.supervisor {
+        Supervisor::Systemd { unit } => {
+            let triggered = result
+                .systemd_metadata
+                .as_ref()
+                .map(|m| m.triggered_by.as_slice())
+                .unwrap_or_default();
+            match triggered.iter().find(|t| t.ends_with(".timer")) {
+                Some(timer) => format!(
+                    "was started by systemd unit {}, which is triggered by timer {}",
+                    unit, timer
+                ),
+                None if !triggered.is_empty() => format!(
+                    "was started by systemd unit {}, activated on demand by {}",
+                    unit,
+                    triggered.join(", ")
+                ),
+                None => format!("was started by systemd unit {}", unit),
+            }
+        }
+        Supervisor::SystemdUser { unit, uid } => format!(
+            "was started by unit {} of {}'s systemd user manager (user@{}.service)",
+            unit,
+            user_name(*uid),
+            uid
+        ),
+        Supervisor::Session(session) => {
+            let mut clause = format!("was started in login session {}", session.id);
+            if let Some(ref user) = session.user {
+                clause.push_str(&format!(" of {}", user));
+            }
+            if let Some(ref service) = session.service {
+                clause.push_str(&format!(" (via {}", service));
+                if let Some(ref host) = session.remote_host {
+                    clause.push_str(&format!(" from {}", host));
+                }
+                if let Some(ref tty) = session.tty {
+                    clause.push_str(&format!(" on {}", tty));
+                }
+                clause.push(')');
+            }
+            clause
+        }
+        Supervisor::Container(container) => {
+            let mut clause = format!(
+                "runs in {} container {}",
+                container.runtime.as_str(),
+                container.display_name()
+            );
+            if let Some(ref image) = container.image {
+                clause.push_str(&format!(" (image {})", image));
+            }
+            clause
+        }
+        Supervisor::Kubernetes { container, pod } => {
+            let pod_name = match (&pod.namespace, &pod.name) {
+                (Some(ns), Some(name)) => format!("{}/{}", ns, name),
+                (None, Some(name)) => name.clone(),
+                _ => pod.uid.clone().unwrap_or_else(|| "?".to_string()),
+            };
+            let mut clause = format!(
+                "runs in container {} of Kubernetes pod {}",
+                container.display_name(),
+                pod_name
+            );
+            if let Some(ref image) = container.image {
+                clause.push_str(&format!(" (image {})", image));
+            }
+            clause
+        }
+        Supervisor::Cron { daemon, pid } => {
+            format!(
+                "was launched as a scheduled job by {} (PID {})",
+                daemon, pid
+            )
+        }
+        Supervisor::Tmux { server_pid, .. } => format!(
+            "was started in a tmux pane and outlives the terminal (tmux server PID {})",
+            server_pid
+        ),
+        Supervisor::Screen { server_pid, .. } => format!(
+            "was started in a screen window and outlives the terminal (screen PID {})",
+            server_pid
+        ),
+        Supervisor::Ssh(ssh) => {
+            let mut clause = "was started from an SSH session".to_string();
+            if let Some(ref ip) = ssh.remote_ip {
+                clause.push_str(&format!(" from {}", ip));
+            }
+            if let Some(ref tty) = ssh.tty {
+                clause.push_str(&format!(" on {}", tty));
+            }
+            clause.push_str(&format!(" (sshd PID {})", ssh.sshd_pid));
+            clause
+        }
+        Supervisor::Supervisord { pid, program } => format!(
+            "is kept running by supervisord as program {} (PID {})",
+            program.as_deref().unwrap_or("?"),
+            pid
+        ),
+        Supervisor::Runit { pid, service } => format!(
+            "is kept running by runit service {} (runsv PID {})",
+            service.as_deref().unwrap_or("?"),
+            pid
+        ),
+        Supervisor::S6 { pid, service } => format!(
+            "is kept running by s6 service {} (s6-supervise PID {})",
+            service.as_deref().unwrap_or("?"),
+            pid
+        ),
+        Supervisor::Init => {
+            "was started by or reparented to init outside any unit (daemonized or orphaned)"
+                .to_string()
+        }
+        Supervisor::Shell => "was started from an interactive shell".to_string(),
+        Supervisor::Unknown if result.process.pid == 1 => "is the init process".to_string(),
+        Supervisor::Unknown => "has no recognizable supervisor".to_string(),
+    }
+}
+
+/// Install and restart facts from systemctl
+fn systemd_clauses(result: &QueryResult) -> Vec<String> {
+    let Some(ref metadata) = result.systemd_metadata else {
+        return Vec::new();
+    };
+
+    let mut clauses = Vec::new();
+
+    if !metadata.wanted_by.is_empty() {
+        clauses.push(format!(
+            "enabled via {} WantedBy",
+            metadata.wanted_by.join(", ")
+        ));
+    }
+
+    match metadata.restart_policy.as_deref() {
+        Some("no") | None => clauses.push("not restarted automatically".to_string()),
+        Some(policy) => clauses.push(format!("restarts {}", policy)),
+    }
+
+    // MainPID=0 means the unit has no main process (e.g. scopes)
+    if let Some(main_pid) = metadata
+        .main_pid
+        .filter(|&p| p > 0 && p != result.process.pid)
+    {
+        clauses.push(format!(
+            "child of the unit's main process (PID {})",
+            main_pid
+        ));
+    }
+
+    clauses
+}
+
+/// Listening and connection facts
+fn network_clauses(result: &QueryResult) -> Vec<String> {
+    let mut public: Vec<String> = Vec::new();
+    let mut local: Vec<String> = Vec::new();
+    let mut established = 0;
+
+    for binding in &result.network_bindings {
+        if is_public_listener(binding) {
+            let port = format!(":{}", binding.local_port);
+            if !public.contains(&port) {
+                public.push(port);
+            }
+        } else if is_listening(binding) {
+            let addr = format_socket_addr(&binding.local_addr, binding.local_port);
+            if !local.contains(&addr) {
+                local.push(addr);
+            }
+        } else if binding.state == ConnectionState::Established {
+            established += 1;
+        }
+    }
+
+    let mut clauses = Vec::new();
+    if !public.is_empty() {
+        clauses.push(format!("listening publicly on {}", public.join(", ")));
+    }
+    if !local.is_empty() {
+        clauses.push(format!("listening on {}", local.join(", ")));
+    }
+    if established > 0 {
+        clauses.push(format!("{} established connection(s)", established));
+    }
+    clauses
+}
+
+/// "bash (1234) ← sshd (1200) ← systemd (1)", skipping the process itself
+fn parent_chain(chain: &[ProcessInfo]) -> Option<String> {
+    let parents: Vec<String> = chain
+        .iter()
+        .rev() // chain is init → … → process
+        .skip(1)
+        .map(|p| format!("{} ({})", p.name, p.pid))
+        .collect();
+
+    (!parents.is_empty()).then(|| parents.join(" ← "))
+}
@@ -0,0 +1,582 @@
+use super::collector::format_bytes;
+use super::env::{MASKED_VALUE, is_secret_key, mask_argument};
+use super::explain::explain;
+use super::network::format_socket_addr;
+use super::query::QueryResult;
+use super::security::describe_capabilities;
+use super::supervisor::Supervisor;
+use super::supervisor::{supervisor_details, supervisor_label};
+use super::threads::state_description;
+use super::users::{group_name, user_name};
+use color_eyre::Result;
+
+/// Output format for exported results
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    Text,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Markdown,
+        ExportFormat::Text,
+        ExportFormat::Json,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Text => "text",
+        }
+    }
+
+    /// File extension for exports in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Text => "txt",
+        }
+    }
+
+    /// Next format in the cycle (TUI)
+    pub fn next(&self) -> ExportFormat {
+        let idx = Self::ALL.iter().position(|f| f == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Titled block of "Label: value" lines
+pub struct Section {
+    pub title: &'static str,
+    pub lines: Vec<String>,
+}
+
+/// Sections selected with `--env`, `--net` and `--tree`
+///
+/// Exports include everything when nothing is selected, otherwise only the
+/// process summary and the selected sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SectionFilter {
+    pub env: bool,
+    pub net: bool,
+    pub tree: bool,
+}
+
+impl SectionFilter {
+    pub fn is_empty(&self) -> bool {
+        !(self.env || self.net || self.tree)
+    }
+
+    /// Check whether an optional section was selected
+    pub fn selects(&self, title: &str) -> bool {
+        match title {
+            "Environment" => self.env,
+            "Network" | "Unix sockets" => self.net,
+            "Ancestor chain" => self.tree,
+            _ => false,
+        }
+    }
+
+    /// Check whether an export includes a section
+    fn includes(&self, title: &str) -> bool {
+        self.is_empty() || title == "Process" || self.selects(title)
+    }
+
+    /// Check whether a JSON export includes a QueryResult field
+    fn includes_field(&self, field: &str) -> bool {
+        match field {
+            "environment" | "parent_environment" => self.includes("Environment"),
+            "network_bindings" | "unix_sockets" => self.includes("Network"),
+            "ancestor_chain" => self.includes("Ancestor chain"),
+            "process" => true,
+            _ => self.is_empty(),
+        }
+    }
+}
+
+/// Render results in the given format
+///
+/// Secret-looking environment values and command-line assignments are
+/// always masked (regardless of the UI's reveal toggle), so exports can be
+/// pasted into tickets as-is.
+pub fn export_results(
+    results: &[QueryResult],
+    format: ExportFormat,
+    filter: SectionFilter,
+) -> Result<String> {
+    let masked: Vec<QueryResult> = results.iter().map(mask_secrets).collect();
+    let results = masked.as_slice();
+
+    Ok(match format {
+        ExportFormat::Json => {
+            let mut value = serde_json::to_value(results)?;
+            if let serde_json::Value::Array(ref mut items) = value {
+                for item in items {
+                    if let serde_json::Value::Object(fields) = item {
+                        fields.retain(|field, _| filter.includes_field(field));
+                    }
+                }
+            }
+            let mut json = serde_json::to_string_pretty(&value)?;
+            json.push('\n');
+            json
+        }
+        ExportFormat::Markdown => results
+            .iter()
+            .map(|r| markdown(r, filter))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ExportFormat::Text => results
+            .iter()
+            .map(|r| text(r, filter))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    })
+}
+
+/// Copy of a result with secret environment values and arguments replaced
+pub fn mask_secrets(result: &QueryResult) -> QueryResult {
+    let mask = |env: &mut std::collections::HashMap<String, String>| {
+        for (key, value) in env.iter_mut() {
+            if is_secret_key(key) {
+                *value = MASKED_VALUE.to_string();
+            }
+        }
+    };
+
+    let mask_cmdline = |cmdline: &mut Vec<String>| {
+        for arg in cmdline.iter_mut() {
+            *arg = mask_argument(arg);
+        }
+    };
+
+    let mut result = result.clone();
+    mask(&mut result.environment);
+    if let Some(ref mut parent) = result.parent_environment {
+        mask(parent);
+    }
+    mask_cmdline(&mut result.process.cmdline);
+    for ancestor in &mut result.ancestor_chain {
+        mask_cmdline(&mut ancestor.cmdline);
+    }
+    result
+}
+
+fn markdown(result: &QueryResult, filter: SectionFilter) -> String {
+    let mut out = format!(
+        "## {} (PID {})\n\n",
+        result.process.name, result.process.pid
+    );
+    for section in sections(result)
+        .into_iter()
+        .filter(|s| filter.includes(s.title))
+    {
+        out.push_str(&format!("### {}\n\n", section.title));
+        for line in section.lines {
+            out.push_str(&format!("- {}\n", line));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn text(result: &QueryResult, filter: SectionFilter) -> String {
+    let mut out = format!("{} (PID {})\n\n", result.process.name, result.process.pid);
+    for section in sections(result)
+        .into_iter()
+        .filter(|s| filter.includes(s.title))
+    {
+        out.push_str(&format!("=== {} ===\n\n", section.title.to_uppercase()));
+        for line in section.lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// All sections of a result, in details-panel order; empty ones are left out
+pub fn sections(result: &QueryResult) -> Vec<Section> {
+    let process = &result.process;
+    let mut sections = vec![
+        Section {
+            title: "Why is this running?",
+            lines: explain(result),
+        },
+        process_section(result),
+        memory_section(result),
+        Section {
+            title: "Ancestor chain",
+            lines: result
+                .ancestor_chain
+                .iter()
+                .map(|p| format!("{} (PID {}, {})", p.name, p.pid, p.user))
+                .collect(),
+        },
+        supervisor_section(&process.supervisor),
+        Section {
+            title: "Network",
+            lines: result
+                .network_bindings
+                .iter()
+                .map(|b| {
+                    let local = format_socket_addr(&b.local_addr, b.local_port);
+                    match (b.remote_addr, b.remote_port) {
+                        (Some(addr), Some(port)) => format!(
+                            "{} {} -> {} {}",
+                            b.protocol.as_str(),
+                            local,
+                            format_socket_addr(&addr, port),
+                            b.state.as_str()
+                        ),
+                        _ => format!("{} {} {}", b.protocol.as_str(), local, b.state.as_str()),
+                    }
+                })
+                .collect(),
+        },
+        Section {
+            title: "Unix sockets",
+            lines: result
+                .unix_sockets
+                .iter()
+                .map(|s| {
+                    let mut line = format!(
+                        "{} {}",
+                        s.socket_type.as_str(),
+                        s.path.as_deref().unwrap_or("(unnamed)")
+                    );
+                    if s.listening {
+                        line.push_str(" LISTEN");
+                    }
+                    if let Some(ref peer) = s.peer {
+                        line.push_str(&format!(" peer {} (PID {})", peer.name, peer.pid));
+                    }
+                    line
+                })
+                .collect(),
+        },
+        Section {
+            title: "Open files",
+            lines: result
+                .open_files
+                .iter()
+                .map(|f| {
+                    format!(
+                        "fd {} {} {} {}{}",
+                        f.fd,
+                        f.mode,
+                        f.kind.as_str(),
+                        f.target,
+                        if f.deleted { " (deleted)" } else { "" }
+                    )
+                })
+                .collect(),
+        },
+        security_section(result),
+        Section {
+            title: "Namespaces",
+            lines: result
+                .security
+                .namespaces
+                .iter()
+                .map(|ns| {
+                    let scope = match ns.differs_from_init {
+                        Some(true) => " (isolated)",
+                        Some(false) => " (host)",
+                        None => "",
+                    };
+                    format!("{}: {}{}", ns.kind, ns.inode, scope)
+                })
+                .collect(),
+        },
+        Section {
+            title: "Limits",
+            lines: result
+                .security
+                .limits
+                .iter()
+                .map(|l| {
+                    let value =
+                        |v: Option<u64>| v.map_or("unlimited".to_string(), |v| v.to_string());
+                    let mut line = format!(
+                        "{}: soft {}, hard {} {}",
+                        l.name,
+                        value(l.soft),
+                        value(l.hard),
+                        l.unit
+                    );
+                    if let Some(usage) = l.usage {
+                        line.push_str(&format!(", in use {}", usage));
+                    }
+                    line
+                })
+                .collect(),
+        },
+        systemd_section(result),
+        io_section(result),
+        Section {
+            title: "Threads",
+            lines: result
+                .threads
+                .iter()
+                .map(|t| {
+                    format!(
+                        "{} {} {} ({}) cpu {:.2}s{}",
+                        t.tid,
+                        t.name,
+                        t.state,
+                        state_description(t.state),
+                        t.cpu_time,
+                        t.wchan
+                            .as_ref()
+                            .map(|w| format!(" wchan {}", w))
+                            .unwrap_or_default()
+                    )
+                })
+                .collect(),
+        },
+        environment_section(result),
+        Section {
+            title: "Warnings",
+            lines: process
+                .warnings
+                .iter()
+                .map(|w| format!("{} {}", w.symbol(), w.description()))
+                .collect(),
+        },
+    ];
+
+    sections.retain(|s| !s.lines.is_empty());
+    sections
+}
+
+fn process_section(result: &QueryResult) -> Section {
+    let process = &result.process;
+    let mut lines = vec![
+        format!("PID: {} (PPID {})", process.pid, process.ppid),
+        format!("Name: {}", process.name),
+        format!("Command: {}", process.cmdline.join(" ")),
+        format!(
+            "State: {} ({})",
+            process.state,
+            state_description(process.state)
+        ),
+        format!("User: {}", process.uids.describe(user_name)),
+        format!("Group: {}", process.gids.describe(group_name)),
+        format!("CPU: {:.1}%", process.cpu_percent),
+        format!("Uptime: {}", process.uptime_str()),
+    ];
+    if let Some(ref cwd) = result.working_directory {
+        lines.push(format!("CWD: {}", cwd));
+    }
+    if result.exited {
+        lines.push("Exited: yes".to_string());
+    }
+    Section {
+        title: "Process",
+        lines,
+    }
+}
+
+fn memory_section(result: &QueryResult) -> Section {
+    let memory = &result.memory;
+    let mut lines = vec![format!("RSS: {}", result.process.memory_str())];
+    let mut push_bytes = |label: &str, value: Option<u64>| {
+        if let Some(bytes) = value {
+            lines.push(format!("{}: {}", label, format_bytes(bytes)));
+        }
+    };
+    push_bytes("PSS", memory.pss);
+    push_bytes("USS", memory.uss);
+    push_bytes("Swap", memory.swap);
+
+    if let Some(score) = memory.oom_score {
+        lines.push(format!(
+            "OOM score: {} (adj {})",
+            score,
+            memory.oom_score_adj.unwrap_or(0)
+        ));
+    }
+    if let Some(ref cgroup) = memory.cgroup {
+        let limit = cgroup.limit.map_or("unlimited".to_string(), format_bytes);
+        lines.push(format!(
+            "Cgroup: {} ({} of {})",
+            cgroup.path,
+            format_bytes(cgroup.current),
+            limit
+        ));
+    }
+    Section {
+        title: "Memory",
+        lines,
+    }
+}
+
+fn supervisor_section(supervisor: &Supervisor) -> Section {
+    let mut lines = vec![format!("Supervisor: {}", supervisor_label(supervisor))];
+    lines.extend(
+        supervisor_details(supervisor)
+            .into_iter()
+            .map(|(label, value)| format!("{} {}", label.trim_end(), value)),
+    );
+
+    let container = match supervisor {
+        Supervisor::Container(container) => Some(container),
+        Supervisor::Kubernetes { container, .. } => Some(container),
+        _ => None,
+    };
+    if let Some(container) = container {
+        lines.push(format!("Container ID: {}", container.id));
+        if let Some(ref image) = container.image {
+            lines.push(format!("Image: {}", image));
+        }
+    }
+    if let Supervisor::Kubernetes { pod, .. } = supervisor {
+        if let Some(ref namespace) = pod.namespace {
+            lines.push(format!("Namespace: {}", namespace));
+        }
+        if let Some(ref name) = pod.name {
+            lines.push(format!("Pod: {}", name));
+        }
+    }
+    Section {
+        title: "Supervisor",
+        lines,
+    }
+}
+
+fn security_section(result: &QueryResult) -> Section {
+    let security = &result.security;
+    let mut lines = Vec::new();
+    if let Some(mask) = security.cap_effective {
+        lines.push(format!(
+            "Effective capabilities: {}",
+            describe_capabilities(mask)
+        ));
+    }
+    if let Some(mask) = security.cap_permitted {
+        lines.push(format!(
+            "Permitted capabilities: {}",
+            describe_capabilities(mask)
+        ));
+    }
+    if let Some(seccomp) = security.seccomp {
+        lines.push(format!("Seccomp: {}", seccomp.as_str()));
+    }
+    if let Some(no_new_privs) = security.no_new_privs {
+        lines.push(format!(
+            "NoNewPrivs: {}",
+            if no_new_privs { "yes" } else { "no" }
+        ));
+    }
+    Section {
+        title: "Security",
+        lines,
+    }
+}
+
+fn systemd_section(result: &QueryResult) -> Section {
+    let Some(ref meta) = result.systemd_metadata else {
+        return Section {
+            title: "Systemd",
+            lines: Vec::new(),
+        };
+    };
+
+    let mut lines = vec![
+        format!("Unit: {}", meta.unit_name),
+        format!(
+            "State: {} ({}), {}",
+            meta.active_state, meta.sub_state, meta.load_state
+        ),
+    ];
+    let optional = [
+        ("Description", meta.description.clone()),
+        ("Main PID", meta.main_pid.map(|pid| pid.to_string())),
+        ("ExecStart", meta.exec_start.clone()),
+        ("Restart", meta.restart_policy.clone()),
+        ("Restarts", meta.n_restarts.map(|n| n.to_string())),
+        ("Last exit", meta.last_exit.clone()),
+        ("Memory", meta.memory_current.map(format_bytes)),
+        (
+            "CPU time",
+            meta.cpu_usage_nsec
+                .map(|ns| format!("{:.1}s", ns as f64 / 1e9)),
+        ),
+        ("Tasks", meta.tasks_current.map(|n| n.to_string())),
+        ("Unit file", meta.fragment_path.clone()),
+    ];
+    lines.extend(
+        optional
+            .into_iter()
+            .filter_map(|(label, value)| value.map(|v| format!("{}: {}", label, v))),
+    );
+
+    let lists = [
+        ("WantedBy", &meta.wanted_by),
+        ("TriggeredBy", &meta.triggered_by),
+        ("Requires", &meta.requires),
+        ("After", &meta.after),
+        ("PartOf", &meta.part_of),
+        ("Drop-ins", &meta.drop_in_paths),
+    ];
+    lines.extend(
+        lists
+            .into_iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(label, values)| format!("{}: {}", label, values.join(", "))),
+    );
+
+    Section {
+        title: "Systemd",
+        lines,
+    }
+}
+
+fn io_section(result: &QueryResult) -> Section {
+    let mut lines = Vec::new();
+    if let Some(ref io) = result.io {
+        lines.push(format!(
+            "Read: {} ({} from disk), {} syscalls",
+            format_bytes(io.rchar),
+            format_bytes(io.read_bytes),
+            io.syscr
+        ));
+        lines.push(format!(
+            "Written: {} ({} to disk, {} cancelled), {} syscalls",
+            format_bytes(io.wchar),
+            format_bytes(io.write_bytes),
+            format_bytes(io.cancelled_write_bytes),
+            io.syscw
+        ));
+    }
+    if let Some(ref rates) = result.io_rates {
+        lines.push(format!(
+            "Rate: read {}/s, write {}/s",
+            format_bytes(rates.rchar as u64),
+            format_bytes(rates.wchar as u64)
+        ));
+    }
+    Section {
+        title: "I/O",
+        lines,
+    }
+}
+
+fn environment_section(result: &QueryResult) -> Section {
+    let mut env_vars: Vec<_> = result.environment.iter().collect();
+    env_vars.sort_by_key(|(k, _)| *k);
+    Section {
+        title: "Environment",
+        lines: env_vars
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect(),
+    }
+}
@@ -0,0 +1,318 @@
+use color_eyre::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::os::unix::fs::MetadataExt;
+
+/// Kind of object behind a file descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FdKind {
+    File,
+    Directory,
+    Pipe,
+    Socket,
+    AnonInode,
+    Device,
+    Other,
+}
+
+impl FdKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Directory => "dir",
+            FdKind::Pipe => "pipe",
+            FdKind::Socket => "socket",
+            FdKind::AnonInode => "anon",
+            FdKind::Device => "dev",
+            FdKind::Other => "other",
+        }
+    }
+}
+
+/// Open file descriptor of a process (/proc/[pid]/fd + fdinfo)
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub target: String, // symlink target, without the " (deleted)" marker
+    pub kind: FdKind,
+    pub mode: String, // "r", "w" or "rw", plus flags like "append"
+    pub offset: u64,
+    pub deleted: bool,
+}
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+// open(2) flags as reported (octal) in /proc/[pid]/fdinfo
+const O_ACCMODE: u32 = 0o3;
+const O_APPEND: u32 = 0o2000;
+const O_NONBLOCK: u32 = 0o4000;
+const O_CLOEXEC: u32 = 0o2000000;
+
+/// Classify a symlink target from /proc/[pid]/fd
+fn classify_target(target: &str, fd_path: &str) -> FdKind {
+    if target.starts_with("socket:[") {
+        FdKind::Socket
+    } else if target.starts_with("pipe:[") {
+        FdKind::Pipe
+    } else if target.starts_with("anon_inode:") {
+        FdKind::AnonInode
+    } else if target.starts_with("/dev/") {
+        FdKind::Device
+    } else if target.starts_with('/') {
+        // Follow the fd itself: works for deleted files too
+        match std::fs::metadata(fd_path) {
+            Ok(meta) if meta.is_dir() => FdKind::Directory,
+            _ => FdKind::File,
+        }
+    } else {
+        FdKind::Other
+    }
+}
+
+/// Decode the octal "flags:" value of fdinfo into a mode string
+fn format_mode(flags: u32) -> String {
+    let mut mode = match flags & O_ACCMODE {
+        0 => "r".to_string(),
+        1 => "w".to_string(),
+        _ => "rw".to_string(),
+    };
+
+    for (flag, name) in [
+        (O_APPEND, "append"),
+        (O_NONBLOCK, "nonblock"),
+        (O_CLOEXEC, "cloexec"),
+    ] {
+        if flags & flag != 0 {
+            mode.push(',');
+            mode.push_str(name);
+        }
+    }
+
+    mode
+}
+
+/// Read offset and flags from /proc/[pid]/fdinfo/[fd]
+fn read_fdinfo(pid: u32, fd: u32) -> (u64, Option<u32>) {
+    let mut offset = 0;
+    let mut flags = None;
+
+    if let Ok(content) = std::fs::read_to_string(format!("/proc/{}/fdinfo/{}", pid, fd)) {
+        for line in content.lines() {
+            if let Some(pos) = line.strip_prefix("pos:") {
+                offset = pos.trim().parse().unwrap_or(0);
+            } else if let Some(f) = line.strip_prefix("flags:") {
+                flags = u32::from_str_radix(f.trim(), 8).ok();
+            }
+        }
+    }
+
+    (offset, flags)
+}
+
+/// Get all open file descriptors of a process, sorted by fd number
+pub fn get_process_open_files(pid: u32) -> Result<Vec<OpenFile>> {
+    let mut files = Vec::new();
+
+    for entry in std::fs::read_dir(format!("/proc/{}/fd", pid))?.flatten() {
+        let Ok(fd) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(link_target) = std::fs::read_link(entry.path()) else {
+            continue;
+        };
+
+        let raw = link_target.to_string_lossy();
+        let (target, deleted) = match raw.strip_suffix(DELETED_SUFFIX) {
+            Some(path) if path.starts_with('/') => (path.to_string(), true),
+            _ => (raw.to_string(), false),
+        };
+
+        let kind = classify_target(&target, &entry.path().to_string_lossy());
+        let (offset, flags) = read_fdinfo(pid, fd);
+
+        files.push(OpenFile {
+            fd,
+            target,
+            kind,
+            mode: flags.map(format_mode).unwrap_or_else(|| "?".to_string()),
+            offset,
+            deleted,
+        });
+    }
+
+    files.sort_by_key(|f| f.fd);
+    Ok(files)
+}
+
+/// Find all processes holding a path open (like `fuser`)
+///
+/// Matches by path (including deleted files, whose fd target keeps the old
+/// path) and by device/inode when the path still exists, so hard links and
+/// bind mounts are found too. A directory also matches files opened below it.
+pub fn find_processes_by_file(path: &str) -> Result<Vec<u32>> {
+    let path = path.trim_end_matches('/');
+    let dir_prefix = format!("{}/", path);
+    let identity = std::fs::metadata(path).ok().map(|m| (m.dev(), m.ino()));
+
+    let mut pids = HashSet::new();
+
+    for entry in std::fs::read_dir("/proc")?.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(fd_dir) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+            continue;
+        };
+
+        for fd_entry in fd_dir.flatten() {
+            let Ok(link_target) = std::fs::read_link(fd_entry.path()) else {
+                continue;
+            };
+            let raw = link_target.to_string_lossy();
+            let target = raw.strip_suffix(DELETED_SUFFIX).unwrap_or(&raw);
+
+            let matches = target == path
+                || target.starts_with(&dir_prefix)
+                || identity.is_some_and(|id| {
+                    std::fs::metadata(fd_entry.path()).is_ok_and(|m| (m.dev(), m.ino()) == id)
+                });
+
+            if matches {
+                pids.insert(pid);
+                break;
+            }
+        }
+    }
+
+    let mut pids: Vec<u32> = pids.into_iter().collect();
+    pids.sort_unstable();
+    Ok(pids)
+}
+
+/// Profiles whose current generation is compared against Nix store mappings
+fn nix_profiles() -> Vec<std::path::PathBuf> {
+    let mut profiles = vec![
+        std::path::PathBuf::from("/run/current-system/sw"),
+        std::path::PathBuf::from("/nix/var/nix/profiles/default"),
+    ];
+    if let Some(home) = dirs::home_dir() {
+        profiles.push(home.join(".nix-profile"));
+    }
+    profiles
+}
+
+/// Package name of a store entry: "<hash>-openssl-3.0.13" → "openssl"
+///
+/// Like Nix's parseDrvName, the version starts at the first dash that is
+/// followed by a digit.
+fn nix_package_name(entry: &str) -> &str {
+    let name = entry.split_once('-').map_or(entry, |(_, name)| name);
+    name.match_indices('-')
+        .find(|&(i, _)| name[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        .map_or(name, |(i, _)| &name[..i])
+}
+
+/// Check whether a Nix store path was superseded in the current profile
+///
+/// Store paths are immutable and never show up as "(deleted)" after an
+/// upgrade; instead the profile points at a new store path. A mapping
+/// "/nix/store/<hash>-pkg/lib/libfoo.so" is stale when some profile's
+/// "lib/libfoo.so" resolves into a different store directory of the same
+/// package (another package providing the same file is not an upgrade).
+fn is_superseded_nix_path(path: &str) -> bool {
+    let Some(rest) = path.strip_prefix("/nix/store/") else {
+        return false;
+    };
+    let Some((entry, relative)) = rest.split_once('/') else {
+        return false;
+    };
+
+    nix_profiles().iter().any(|profile| {
+        std::fs::canonicalize(profile.join(relative)).is_ok_and(|current| {
+            let current = current.to_string_lossy();
+            let Some((current_entry, _)) = current
+                .strip_prefix("/nix/store/")
+                .and_then(|rest| rest.split_once('/'))
+            else {
+                return false;
+            };
+            current_entry != entry && nix_package_name(current_entry) == nix_package_name(entry)
+        })
+    })
+}
+
+/// Find the deleted or replaced executable and libraries a process still runs
+///
+/// Looks at /proc/[pid]/exe and file-backed mappings in /proc/[pid]/maps, the
+/// same signals `needrestart` uses after package upgrades.
+pub fn find_stale_files(pid: u32) -> Vec<String> {
+    let mut stale: Vec<String> = Vec::new();
+    let mut push = |path: &str| {
+        if !stale.iter().any(|p| p == path) {
+            stale.push(path.to_string());
+        }
+    };
+
+    if let Ok(exe) = std::fs::read_link(format!("/proc/{}/exe", pid)) {
+        let exe = exe.to_string_lossy();
+        match exe.strip_suffix(DELETED_SUFFIX) {
+            Some(path) => push(path),
+            None if is_superseded_nix_path(&exe) => push(&exe),
+            None => {}
+        }
+    }
+
+    let maps = std::fs::read_to_string(format!("/proc/{}/maps", pid)).unwrap_or_default();
+
+    let mut seen_nix: HashSet<&str> = HashSet::new();
+    for line in maps.lines() {
+        // address perms offset dev inode pathname
+        let Some(path) = line.splitn(6, char::is_whitespace).nth(5).map(str::trim) else {
+            continue;
+        };
+
+        if let Some(deleted) = path.strip_suffix(DELETED_SUFFIX) {
+            // Shared memory and memfd mappings are routinely unlinked
+            if deleted.starts_with('/')
+                && !deleted.starts_with("/dev/shm/")
+                && !deleted.starts_with("/SYSV")
+                && !deleted.starts_with("/memfd:")
+            {
+                push(deleted);
+            }
+        } else if path.starts_with("/nix/store/")
+            && seen_nix.insert(path)
+            && is_superseded_nix_path(path)
+        {
+            push(path);
+        }
+    }
+
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nix_package_name_strips_hash_and_version() {
+        assert_eq!(
+            nix_package_name("0c9dj2ik4l8vmq4avf8fw5ljijhdsy6r-openssl-3.0.13"),
+            "openssl"
+        );
+        assert_eq!(
+            nix_package_name("9gq1vdzb2c5jrxhkmchfj6bsyn4y0dz1-gtk+3-3.24.41"),
+            "gtk+3"
+        );
+        assert_eq!(
+            nix_package_name("x7m3adcfs6bmk6dc8w1jzx1rmfxkw2ya-python3.11-requests-2.31.0-dist"),
+            "python3.11-requests"
+        );
+        assert_eq!(
+            nix_package_name("kq7zglxvnmxjjs8bsmz0mmdlw4fqz0rb-hello"),
+            "hello"
+        );
+    }
+}
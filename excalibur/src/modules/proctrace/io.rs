@@ -0,0 +1,66 @@
+use procfs::process::Process;
+use serde::Serialize;
+use std::time::Instant;
+
+/// I/O counters from /proc/<pid>/io
+///
+/// rchar/wchar count every read(2)/write(2) (including sockets, pipes and
+/// page-cache hits); read_bytes/write_bytes only what reached the block layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct IoStats {
+    pub rchar: u64,
+    pub wchar: u64,
+    pub syscr: u64,
+    pub syscw: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub cancelled_write_bytes: u64,
+    #[serde(skip)]
+    pub sampled_at: Instant,
+}
+
+/// Per-second I/O rates between two samples (watch mode)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct IoRates {
+    pub rchar: f64,
+    pub wchar: f64,
+    pub syscr: f64,
+    pub syscw: f64,
+    pub read_bytes: f64,
+    pub write_bytes: f64,
+}
+
+impl IoRates {
+    /// Compute rates from an older and a newer sample of the same process
+    pub fn between(old: &IoStats, new: &IoStats) -> Option<Self> {
+        let secs = new.sampled_at.duration_since(old.sampled_at).as_secs_f64();
+        if secs <= 0.0 {
+            return None;
+        }
+        let rate = |old: u64, new: u64| new.saturating_sub(old) as f64 / secs;
+
+        Some(Self {
+            rchar: rate(old.rchar, new.rchar),
+            wchar: rate(old.wchar, new.wchar),
+            syscr: rate(old.syscr, new.syscr),
+            syscw: rate(old.syscw, new.syscw),
+            read_bytes: rate(old.read_bytes, new.read_bytes),
+            write_bytes: rate(old.write_bytes, new.write_bytes),
+        })
+    }
+}
+
+/// Read I/O counters (needs same user or root)
+pub fn read_io_stats(pid: u32) -> Option<IoStats> {
+    let io = Process::new(pid as i32).ok()?.io().ok()?;
+    Some(IoStats {
+        rchar: io.rchar,
+        wchar: io.wchar,
+        syscr: io.syscr,
+        syscw: io.syscw,
+        read_bytes: io.read_bytes,
+        write_bytes: io.write_bytes,
+        cancelled_write_bytes: io.cancelled_write_bytes,
+        sampled_at: Instant::now(),
+    })
+}
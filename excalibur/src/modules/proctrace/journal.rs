@@ -0,0 +1,386 @@
+use super::supervisor::Supervisor;
+use color_eyre::Result;
+use serde_json::Value;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Number of lines loaded when the journal pane is opened
+pub const DEFAULT_LINES: usize = 200;
+
+/// Upper bound on lines loaded (`more_lines`) or kept in follow mode
+const MAX_LINES: usize = 10_000;
+
+/// Minimum time between journalctl runs in follow mode (ticks are much faster)
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Syslog priority names, indexed by level (see journalctl(1) --priority)
+pub const PRIORITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// journalctl binary (tests inject a stand-in through `JournalView::with_binary`)
+const JOURNALCTL: &str = "journalctl";
+
+/// Entries from one journalctl run plus the cursor of the last one
+type JournalBatch = (Vec<JournalEntry>, Option<String>);
+
+/// What the journal is filtered by
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalSource {
+    Unit(String),     // system unit (-u)
+    UserUnit(String), // unit of a per-user manager (--user-unit)
+    Pid(u32),         // _PID= match for everything else
+}
+
+impl JournalSource {
+    /// Pick the journal filter for a process: its unit if it has one, else its PID
+    pub fn for_process(pid: u32, supervisor: &Supervisor) -> Self {
+        match supervisor {
+            // Scopes (session-N.scope, docker-*.scope) mostly log under other units
+            Supervisor::Systemd { unit } if unit.ends_with(".service") => {
+                JournalSource::Unit(unit.clone())
+            }
+            Supervisor::SystemdUser { unit, .. } if unit.ends_with(".service") => {
+                JournalSource::UserUnit(unit.clone())
+            }
+            _ => JournalSource::Pid(pid),
+        }
+    }
+
+    fn args(&self) -> Vec<String> {
+        match self {
+            JournalSource::Unit(unit) => vec!["-u".to_string(), unit.clone()],
+            JournalSource::UserUnit(unit) => vec!["--user-unit".to_string(), unit.clone()],
+            JournalSource::Pid(pid) => vec![format!("_PID={}", pid)],
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            JournalSource::Unit(unit) => unit.clone(),
+            JournalSource::UserUnit(unit) => format!("{} (user)", unit),
+            JournalSource::Pid(pid) => format!("_PID={}", pid),
+        }
+    }
+}
+
+/// One journal entry (from `journalctl -o json`)
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub timestamp: Option<u64>, // __REALTIME_TIMESTAMP, µs since epoch
+    pub priority: Option<u8>,
+    pub identifier: Option<String>, // SYSLOG_IDENTIFIER or _COMM
+    pub pid: Option<u32>,
+    pub message: String,
+}
+
+impl JournalEntry {
+    /// Format the timestamp like `journalctl` does ("Oct 18 14:03:22")
+    pub fn time_str(&self) -> String {
+        self.timestamp
+            .and_then(|usec| chrono::DateTime::from_timestamp_micros(usec as i64))
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%b %d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "-".to_string())
+    }
+}
+
+/// Journal pane state: entries for one source plus the read position
+#[derive(Debug)]
+pub struct JournalView {
+    pub source: JournalSource,
+    pub entries: Vec<JournalEntry>,
+    pub cursor: Option<String>, // __CURSOR of the newest entry
+    pub max_priority: u8,       // show levels 0..=max_priority
+    pub lines: usize,
+    pub follow: bool,
+    pub scroll: usize,  // lines scrolled up from the bottom
+    journalctl: String, // JOURNALCTL outside tests
+    last_poll: Instant,
+    pending: Option<Receiver<Result<JournalBatch>>>, // follow-mode run in flight
+}
+
+impl JournalView {
+    pub fn new(source: JournalSource) -> Self {
+        Self::with_binary(source, JOURNALCTL.to_string())
+    }
+
+    /// View that runs an explicit journalctl binary
+    fn with_binary(source: JournalSource, journalctl: String) -> Self {
+        Self {
+            source,
+            entries: Vec::new(),
+            cursor: None,
+            max_priority: 7,
+            lines: DEFAULT_LINES,
+            follow: false,
+            scroll: 0,
+            journalctl,
+            last_poll: Instant::now(),
+            pending: None,
+        }
+    }
+
+    /// Check whether follow mode should start or collect a journalctl run
+    pub fn poll_due(&self) -> bool {
+        self.follow && (self.pending.is_some() || self.last_poll.elapsed() >= FOLLOW_INTERVAL)
+    }
+
+    /// Load the last N lines, replacing what is shown
+    pub fn reload(&mut self) -> Result<()> {
+        let mut args = vec!["-n".to_string(), self.lines.to_string()];
+        args.extend(self.source.args());
+        let (entries, cursor) = run_journalctl(&self.journalctl, &args, self.max_priority)?;
+        self.entries = entries;
+        self.cursor = cursor;
+        self.scroll = 0;
+        self.last_poll = Instant::now();
+        // A follow run started before the reload may use an old cursor or filter
+        self.pending = None;
+        Ok(())
+    }
+
+    /// Follow mode: append entries written since the last read
+    ///
+    /// journalctl runs on a background thread so the UI tick never waits for
+    /// it; each call either starts a run or collects a finished one. Returns
+    /// the number of new entries (0 while a run is in flight).
+    pub fn poll(&mut self) -> Result<usize> {
+        let Some(ref pending) = self.pending else {
+            self.start_poll();
+            return Ok(0);
+        };
+
+        let (new_entries, cursor) = match pending.try_recv() {
+            Ok(batch) => {
+                self.pending = None;
+                batch?
+            }
+            Err(TryRecvError::Empty) => return Ok(0),
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                return Err(color_eyre::eyre::eyre!("journalctl run was aborted"));
+            }
+        };
+        let count = new_entries.len();
+
+        if count > 0 {
+            self.entries.extend(new_entries);
+            self.cursor = cursor;
+            let excess = self.entries.len().saturating_sub(MAX_LINES);
+            self.entries.drain(..excess);
+            // Keep the viewport still while the user is scrolled up
+            if self.scroll > 0 {
+                self.scroll += count;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Start a background journalctl run for entries after the cursor
+    fn start_poll(&mut self) {
+        self.last_poll = Instant::now();
+        let mut args = match self.cursor {
+            Some(ref cursor) => vec![format!("--after-cursor={}", cursor)],
+            None => vec!["-n".to_string(), self.lines.to_string()],
+        };
+        args.extend(self.source.args());
+
+        let (sender, receiver) = mpsc::channel();
+        let journalctl = self.journalctl.clone();
+        let max_priority = self.max_priority;
+        std::thread::spawn(move || {
+            // The receiver is gone when the view was reloaded or closed meanwhile
+            let _ = sender.send(run_journalctl(&journalctl, &args, max_priority));
+        });
+        self.pending = Some(receiver);
+    }
+
+    /// Double the number of lines loaded by `reload`, up to MAX_LINES
+    pub fn more_lines(&mut self) {
+        self.lines = self.lines.saturating_mul(2).min(MAX_LINES);
+    }
+
+    /// Cycle the priority filter: all → debug … → emerg → all
+    pub fn cycle_priority(&mut self) {
+        self.max_priority = if self.max_priority == 0 {
+            7
+        } else {
+            self.max_priority - 1
+        };
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+/// Run journalctl with JSON output and parse one entry per line
+///
+/// Returns the entries and the cursor of the last one.
+fn run_journalctl(journalctl: &str, args: &[String], max_priority: u8) -> Result<JournalBatch> {
+    let mut command = Command::new(journalctl);
+    command.args(["-o", "json", "--no-pager", "-q"]);
+    if max_priority < 7 {
+        command.arg(format!("--priority={}", max_priority));
+    }
+    let output = command.args(args).output()?;
+
+    if !output.status.success() {
+        return Err(color_eyre::eyre::eyre!(
+            "journalctl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let mut entries = Vec::new();
+    let mut cursor = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if let Some(c) = value["__CURSOR"].as_str() {
+            cursor = Some(c.to_string());
+        }
+        entries.push(parse_entry(&value));
+    }
+
+    Ok((entries, cursor))
+}
+
+/// Read a journal field; journalctl emits numbers as strings
+fn field(value: &Value, key: &str) -> Option<String> {
+    value[key].as_str().map(str::to_string)
+}
+
+fn parse_entry(value: &Value) -> JournalEntry {
+    JournalEntry {
+        timestamp: field(value, "__REALTIME_TIMESTAMP").and_then(|t| t.parse().ok()),
+        priority: field(value, "PRIORITY").and_then(|p| p.parse().ok()),
+        identifier: field(value, "SYSLOG_IDENTIFIER").or_else(|| field(value, "_COMM")),
+        pid: field(value, "_PID").and_then(|p| p.parse().ok()),
+        message: decode_message(&value["MESSAGE"]),
+    }
+}
+
+/// MESSAGE is a string, or an array of bytes when it is not valid UTF-8
+fn decode_message(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|b| b.as_u64().map(|b| b as u8))
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::proctrace::test_support::ScratchDir;
+
+    /// Stand-in journalctl: logs its arguments, answers follow-ups after cursor c2
+    const JOURNALCTL: &str = r#"echo "$*" >> "$0.args"
+case "$*" in
+  *--after-cursor=c2*)
+    echo '{"__CURSOR":"c3","__REALTIME_TIMESTAMP":"1700000002000000","PRIORITY":"3","_PID":"7","_COMM":"web","MESSAGE":"third"}'
+    ;;
+  *)
+    echo '{"__CURSOR":"c1","__REALTIME_TIMESTAMP":"1700000000000000","PRIORITY":"6","_PID":"7","SYSLOG_IDENTIFIER":"web","MESSAGE":"first"}'
+    echo 'not json'
+    echo '{"__CURSOR":"c2","PRIORITY":"4","MESSAGE":[104,105,255]}'
+    ;;
+esac"#;
+
+    #[test]
+    fn reload_parses_lines_and_captures_cursor() {
+        let dir = ScratchDir::new("journalctl-reload");
+        let journalctl = dir.script("journalctl", JOURNALCTL);
+        let mut view = JournalView::with_binary(JournalSource::Pid(7), journalctl.clone());
+
+        view.reload().unwrap();
+
+        assert_eq!(view.entries.len(), 2);
+        assert_eq!(view.cursor.as_deref(), Some("c2"));
+        let first = &view.entries[0];
+        assert_eq!(first.timestamp, Some(1_700_000_000_000_000));
+        assert_eq!(first.priority, Some(6));
+        assert_eq!(first.identifier.as_deref(), Some("web"));
+        assert_eq!(first.pid, Some(7));
+        assert_eq!(first.message, "first");
+        assert_eq!(view.entries[1].message, "hi\u{fffd}");
+
+        let args = std::fs::read_to_string(format!("{}.args", journalctl)).unwrap();
+        assert_eq!(args.trim(), "-o json --no-pager -q -n 200 _PID=7");
+    }
+
+    #[test]
+    fn poll_continues_after_cursor() {
+        let dir = ScratchDir::new("journalctl-poll");
+        let journalctl = dir.script("journalctl", JOURNALCTL);
+        let mut view = JournalView::with_binary(
+            JournalSource::Unit("web.service".to_string()),
+            journalctl.clone(),
+        );
+        view.max_priority = 4;
+        view.reload().unwrap();
+        view.scroll = 1;
+        view.follow = true;
+        assert!(!view.poll_due());
+
+        // Starts a background run on the first poll, collects it on a later one
+        view.last_poll -= FOLLOW_INTERVAL;
+        assert!(view.poll_due());
+        assert_eq!(view.poll().unwrap(), 0);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let count = loop {
+            assert!(view.poll_due());
+            match view.poll().unwrap() {
+                0 if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+                count => break count,
+            }
+        };
+
+        assert_eq!(count, 1);
+        assert!(!view.poll_due());
+        assert_eq!(view.entries.len(), 3);
+        assert_eq!(view.entries[2].message, "third");
+        assert_eq!(view.entries[2].identifier.as_deref(), Some("web"));
+        assert_eq!(view.cursor.as_deref(), Some("c3"));
+        assert_eq!(view.scroll, 2);
+
+        let args = std::fs::read_to_string(format!("{}.args", journalctl)).unwrap();
+        let calls: Vec<&str> = args.lines().collect();
+        assert_eq!(
+            calls,
+            [
+                "-o json --no-pager -q --priority=4 -n 200 -u web.service",
+                "-o json --no-pager -q --priority=4 --after-cursor=c2 -u web.service",
+            ]
+        );
+    }
+
+    #[test]
+    fn more_lines_is_bounded() {
+        let mut view = JournalView::new(JournalSource::Pid(1));
+        view.more_lines();
+        assert_eq!(view.lines, DEFAULT_LINES * 2);
+
+        view.lines = usize::MAX;
+        view.more_lines();
+        assert_eq!(view.lines, MAX_LINES);
+    }
+}
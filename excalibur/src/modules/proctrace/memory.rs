@@ -0,0 +1,149 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// cgroup v2 mount point (v1 controllers live in subdirectories of it)
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// cgroup v1 reports "no limit" as a page-rounded i64::MAX
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// cgroup usage at or above this fraction of the limit triggers a warning
+pub const NEAR_CGROUP_LIMIT_RATIO: f64 = 0.9;
+
+/// oom_score (0..=2000) at or above which a warning fires
+pub const HIGH_OOM_SCORE: u32 = 500;
+
+/// Memory accounting of the cgroup a process runs in
+#[derive(Debug, Clone, Serialize)]
+pub struct CgroupMemory {
+    pub path: String,       // cgroup path, e.g. /system.slice/nginx.service
+    pub current: u64,       // bytes charged to the cgroup
+    pub limit: Option<u64>, // tightest limit on the path to the root, None = unlimited
+}
+
+impl CgroupMemory {
+    /// Usage as a fraction of the limit
+    pub fn usage_ratio(&self) -> Option<f64> {
+        self.limit
+            .filter(|&limit| limit > 0)
+            .map(|limit| self.current as f64 / limit as f64)
+    }
+
+    pub fn is_near_limit(&self) -> bool {
+        self.usage_ratio()
+            .is_some_and(|ratio| ratio >= NEAR_CGROUP_LIMIT_RATIO)
+    }
+}
+
+/// Detailed memory figures beyond RSS
+///
+/// PSS splits shared pages between their users, USS counts only pages no
+/// other process maps (what is freed when the process exits).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryDetail {
+    pub pss: Option<u64>,  // bytes
+    pub uss: Option<u64>,  // bytes (Private_Clean + Private_Dirty)
+    pub swap: Option<u64>, // bytes
+    pub oom_score: Option<u32>,
+    pub oom_score_adj: Option<i32>,
+    pub cgroup: Option<CgroupMemory>,
+}
+
+/// Read PSS/USS/swap, OOM scores and cgroup memory for a process
+///
+/// smaps_rollup needs ptrace access (same user or root); other fields are
+/// world-readable.
+pub fn read_memory_detail(pid: u32) -> MemoryDetail {
+    let rollup = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)).unwrap_or_default();
+    let kb_field = |key: &str| -> Option<u64> {
+        rollup
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|kb| kb.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+    };
+
+    let uss = match (kb_field("Private_Clean"), kb_field("Private_Dirty")) {
+        (Some(clean), Some(dirty)) => Some(clean + dirty),
+        _ => None,
+    };
+
+    MemoryDetail {
+        pss: kb_field("Pss"),
+        uss,
+        swap: kb_field("Swap"),
+        oom_score: read_number(format!("/proc/{}/oom_score", pid)),
+        oom_score_adj: read_number(format!("/proc/{}/oom_score_adj", pid)),
+        cgroup: read_cgroup_memory(pid),
+    }
+}
+
+fn read_number<T: std::str::FromStr>(path: impl AsRef<Path>) -> Option<T> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Read a limit file; "max" (v2) and huge values (v1) mean unlimited
+fn read_limit(path: &Path) -> Option<u64> {
+    let value = std::fs::read_to_string(path).ok()?;
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|&limit| limit < V1_UNLIMITED)
+}
+
+/// The tightest limit on the way from a cgroup up to the hierarchy root
+fn tightest_limit(root: &Path, path: &str, file: &str) -> Option<u64> {
+    let mut dir: PathBuf = root.join(path.trim_start_matches('/'));
+    let mut limit: Option<u64> = None;
+
+    while dir.starts_with(root) {
+        if let Some(l) = read_limit(&dir.join(file)) {
+            limit = Some(limit.map_or(l, |current| current.min(l)));
+        }
+        if !dir.pop() {
+            break;
+        }
+    }
+
+    limit
+}
+
+/// Find the memory cgroup of a process (v2 unified, or the v1 memory controller)
+fn read_cgroup_memory(pid: u32) -> Option<CgroupMemory> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    let root = Path::new(CGROUP_ROOT);
+
+    for line in content.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+
+        if controllers.split(',').any(|c| c == "memory") {
+            // cgroup v1
+            let v1_root = root.join("memory");
+            let dir = v1_root.join(path.trim_start_matches('/'));
+            let current = read_number(dir.join("memory.usage_in_bytes"))?;
+            return Some(CgroupMemory {
+                path: path.to_string(),
+                current,
+                limit: tightest_limit(&v1_root, path, "memory.limit_in_bytes"),
+            });
+        }
+    }
+
+    // cgroup v2: "0::/path" with memory.current in the unified hierarchy
+    let path = content.lines().find_map(|line| line.strip_prefix("0::"))?;
+    let current = read_number(
+        root.join(path.trim_start_matches('/'))
+            .join("memory.current"),
+    )?;
+    Some(CgroupMemory {
+        path: path.to_string(),
+        current,
+        limit: tightest_limit(root, path, "memory.max"),
+    })
+}
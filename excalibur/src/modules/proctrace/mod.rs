@@ -1,21 +1,86 @@
+mod actions;
+mod cli;
 mod collector;
+mod container;
+mod env;
+mod explain;
+mod export;
+mod files;
+mod io;
+mod journal;
+mod memory;
 mod network;
+mod parser;
 mod query;
+mod security;
 mod state;
+mod supervisor;
 mod systemd;
+#[cfg(test)]
+mod test_support;
+mod threads;
+mod tree;
 mod ui;
+mod users;
 
+use crate::modules::history::clipboard::ClipboardManager;
 use crate::modules::{Module, ModuleAction, ModuleId, ModuleMetadata};
+use actions::ProcessAction;
+pub use cli::run_query;
 use color_eyre::Result;
-use query::QueryEngine;
+use export::export_results;
+pub use export::{ExportFormat, SectionFilter};
+use journal::{JournalSource, JournalView, PRIORITY_NAMES};
+use query::{QueryEngine, SortKey};
 use ratatui::{buffer::Buffer, crossterm::event::KeyEvent, layout::Rect};
 use state::{InputMode, ProcessTracerState};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use supervisor::Supervisor;
 
 /// Process Tracer module (query-driven)
 #[derive(Debug)]
 pub struct ProcessTracerModule {
     state: ProcessTracerState,
     query_engine: QueryEngine,
+    clipboard: ClipboardManager,
+}
+
+/// Write an export to a new file in the temp directory, readable only by the owner
+///
+/// `create_new` (O_EXCL) neither follows a planted symlink nor overwrites an
+/// existing file; a counter is appended until an unused name is found.
+fn write_private_file(stem: &str, extension: &str, contents: &str) -> std::io::Result<PathBuf> {
+    let dir = std::env::temp_dir();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+
+    for attempt in 0..100 {
+        let name = match attempt {
+            0 => format!("{}-{}.{}", stem, stamp, extension),
+            n => format!("{}-{}-{}.{}", stem, stamp, n, extension),
+        };
+        let path = dir.join(name);
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(contents.as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(std::io::Error::new(
+        ErrorKind::AlreadyExists,
+        "no unused export file name",
+    ))
 }
 
 impl ProcessTracerModule {
@@ -23,6 +88,113 @@ impl ProcessTracerModule {
         Self {
             state: ProcessTracerState::new(),
             query_engine: QueryEngine::new(),
+            clipboard: ClipboardManager::new(),
+        }
+    }
+
+    /// Export the "Why is this running?" narrative of the selected result
+    ///
+    /// Copies to the clipboard; without one (e.g. over SSH) the text is
+    /// written to a file in the temp directory instead.
+    fn export_explanation(&mut self) {
+        let Some(result) = self.state.get_selected_result() else {
+            return;
+        };
+        let text = explain::explain_text(result);
+        let pid = result.process.pid;
+
+        let msg = match self.clipboard.copy(&text) {
+            Ok(()) => "Explanation copied to clipboard".to_string(),
+            Err(_) => match write_private_file(&format!("xcl-why-{}", pid), "txt", &text) {
+                Ok(path) => format!("Explanation saved to {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            },
+        };
+        self.state.set_notification(msg);
+    }
+
+    /// Export the selected result (or all results) in the chosen format
+    ///
+    /// `to_clipboard` copies the text; otherwise it is written to a new
+    /// owner-only file in the temp directory (see [`write_private_file`]).
+    fn export_results(&mut self, all: bool, to_clipboard: bool) {
+        let results = if all {
+            self.state.query_results.as_slice()
+        } else {
+            match self.state.get_selected_result() {
+                Some(result) => std::slice::from_ref(result),
+                None => return,
+            }
+        };
+        if results.is_empty() {
+            return;
+        }
+
+        let format = self.state.export_format;
+        let sections = SectionFilter::default();
+        let text = match export_results(results, format, sections) {
+            Ok(text) => text,
+            Err(e) => {
+                self.state.set_notification(format!("Export failed: {}", e));
+                return;
+            }
+        };
+        let what = if all {
+            format!("{} result(s)", results.len())
+        } else {
+            format!("PID {}", results[0].process.pid)
+        };
+
+        let msg = if to_clipboard {
+            match self.clipboard.copy(&text) {
+                Ok(()) => format!("Copied {} as {}", what, format.label()),
+                Err(e) => format!("Copy failed: {}", e),
+            }
+        } else {
+            let stem = if all {
+                "xcl-pt".to_string()
+            } else {
+                format!("xcl-pt-{}", results[0].process.pid)
+            };
+            match write_private_file(&stem, format.extension(), &text) {
+                Ok(path) => format!("Exported {} to {}", what, path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            }
+        };
+        self.state.set_notification(msg);
+    }
+
+    /// Open the journal pane for the selected result
+    fn open_journal(&mut self) {
+        let Some(result) = self.state.get_selected_result() else {
+            return;
+        };
+        let source = JournalSource::for_process(result.process.pid, &result.process.supervisor);
+        let mut journal = JournalView::new(source);
+
+        match journal.reload() {
+            Ok(()) => {
+                let msg = format!(
+                    "Journal: {} line(s) for {}",
+                    journal.entries.len(),
+                    journal.source.describe()
+                );
+                self.state.open_journal(journal);
+                self.state.set_notification(msg);
+            }
+            Err(e) => {
+                self.state.set_notification(format!("Journal error: {}", e));
+            }
+        }
+    }
+
+    /// Reload the journal pane (after a filter change)
+    fn reload_journal(&mut self) {
+        let Some(ref mut journal) = self.state.journal else {
+            return;
+        };
+        if let Err(e) = journal.reload() {
+            self.state.set_notification(format!("Journal error: {}", e));
         }
     }
 
@@ -33,12 +205,14 @@ impl ProcessTracerModule {
             Ok(q) => q,
             Err(e) => {
                 self.state.set_notification(format!("Invalid query: {}", e));
+                self.state.query_error = Some(e);
                 return Ok(());
             }
         };
+        self.state.query_error = None;
 
         // Execute query
-        match self.query_engine.execute(query) {
+        match self.query_engine.execute(query.clone()) {
             Ok(results) => {
                 if results.is_empty() {
                     self.state
@@ -46,9 +220,12 @@ impl ProcessTracerModule {
                 } else {
                     // Store results and switch to results mode
                     self.state.query_results = results;
+                    self.state.sort_key.sort(&mut self.state.query_results);
                     self.state.selected_result = 0;
                     self.state.scroll_offset = 0;
                     self.state.input_mode = InputMode::ViewResults;
+                    self.state.last_query = Some(query);
+                    self.state.last_refresh = std::time::Instant::now();
 
                     // Add to history
                     self.state.add_to_history(self.state.query_input.clone());
@@ -67,9 +244,89 @@ impl ProcessTracerModule {
         Ok(())
     }
 
+    /// Re-run the last query (watch mode)
+    fn refresh_results(&mut self) {
+        let Some(query) = self.state.last_query.clone() else {
+            return;
+        };
+
+        match self.query_engine.refresh(query, &self.state.query_results) {
+            Ok(results) => self.state.replace_results(results),
+            Err(e) => {
+                self.state.set_notification(format!("Refresh error: {}", e));
+                self.state.last_refresh = std::time::Instant::now();
+            }
+        }
+    }
+
+    /// Open tree mode for all processes, or for the given roots and their descendants
+    fn open_tree(&mut self, roots: Option<Vec<u32>>) -> Result<()> {
+        match self.query_engine.build_tree(roots.as_deref()) {
+            Ok(tree) => {
+                let count = tree.process_count();
+                self.state.open_tree(tree);
+                self.state
+                    .set_notification(format!("Tree: {} process(es)", count));
+            }
+            Err(e) => {
+                self.state.set_notification(format!("Tree error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open audit mode listing every publicly bound listener
+    fn open_audit(&mut self) -> Result<()> {
+        match self.query_engine.audit_public_listeners() {
+            Ok(entries) => {
+                let count = entries.len();
+                self.state.open_audit(entries);
+                self.state
+                    .set_notification(format!("{} public listener(s)", count));
+            }
+            Err(e) => {
+                self.state.set_notification(format!("Audit error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open ports mode listing all listeners grouped by port
+    fn open_ports(&mut self) -> Result<()> {
+        match self.query_engine.port_overview() {
+            Ok(groups) => {
+                let count = groups.len();
+                self.state.open_ports(groups);
+                self.state
+                    .set_notification(format!("{} listening port(s)", count));
+            }
+            Err(e) => {
+                self.state
+                    .set_notification(format!("Port scan error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open health mode counting unhealthy process states system-wide
+    fn open_health(&mut self) -> Result<()> {
+        match self.query_engine.health_overview() {
+            Ok(report) => {
+                let count = report.entries.len();
+                self.state.open_health(report);
+                self.state
+                    .set_notification(format!("{} process(es) need attention", count));
+            }
+            Err(e) => {
+                self.state.set_notification(format!("Health error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
     /// Handle key events in query mode
     fn handle_query_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
-        use ratatui::crossterm::event::KeyCode;
+        use ratatui::crossterm::event::{KeyCode, KeyModifiers};
 
         match key.code {
             // Execute query
@@ -78,15 +335,41 @@ impl ProcessTracerModule {
                 Ok(ModuleAction::None)
             }
 
+            // Tree of all processes
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_tree(None)?;
+                Ok(ModuleAction::None)
+            }
+
+            // Security audit of public listeners
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_audit()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Port overview
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_ports()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Health overview
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_health()?;
+                Ok(ModuleAction::None)
+            }
+
             // Input character
             KeyCode::Char(c) => {
                 self.state.query_input.push(c);
+                self.state.query_error = None;
                 Ok(ModuleAction::None)
             }
 
             // Backspace
             KeyCode::Backspace => {
                 self.state.query_input.pop();
+                self.state.query_error = None;
                 Ok(ModuleAction::None)
             }
 
@@ -156,12 +439,535 @@ impl ProcessTracerModule {
                 Ok(ModuleAction::None)
             }
 
+            // Watch mode
+            KeyCode::Char('w') => {
+                self.state.toggle_watch();
+                let msg = if self.state.watch_mode {
+                    format!("Watch mode on ({}s)", self.state.watch_interval.as_secs())
+                } else {
+                    "Watch mode off".to_string()
+                };
+                self.state.set_notification(msg);
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.state.increase_watch_interval();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('-') => {
+                self.state.decrease_watch_interval();
+                Ok(ModuleAction::None)
+            }
+
+            // Details tabs (overview / I/O / threads)
+            KeyCode::Tab => {
+                self.state.next_details_tab();
+                Ok(ModuleAction::None)
+            }
+
+            // Sort by cpu/rss/pss/uss/swap/oom score/cgroup usage
+            KeyCode::Char('O') => {
+                self.state.cycle_sort();
+                // Back to query order: re-run the query to restore it
+                if self.state.sort_key == SortKey::Default {
+                    self.refresh_results();
+                }
+                self.state
+                    .set_notification(format!("Sort: {}", self.state.sort_key.label()));
+                Ok(ModuleAction::None)
+            }
+
+            // Tree of matches (with descendants) / all processes
+            KeyCode::Char('t') => {
+                let roots = self
+                    .state
+                    .query_results
+                    .iter()
+                    .map(|r| r.process.pid)
+                    .collect();
+                self.open_tree(Some(roots))?;
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('T') => {
+                self.open_tree(None)?;
+                Ok(ModuleAction::None)
+            }
+
+            // Security audit of public listeners
+            KeyCode::Char('a') => {
+                self.open_audit()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Port overview
+            KeyCode::Char('o') => {
+                self.open_ports()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Export selected/all results to a file or the clipboard
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                self.export_results(key.code == KeyCode::Char('X'), false);
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.export_results(key.code == KeyCode::Char('Y'), true);
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('f') => {
+                self.state.export_format = self.state.export_format.next();
+                self.state.set_notification(format!(
+                    "Export format: {}",
+                    self.state.export_format.label()
+                ));
+                Ok(ModuleAction::None)
+            }
+
+            // Reveal/mask secret-looking environment values
+            KeyCode::Char('v') => {
+                self.state.reveal_secrets = !self.state.reveal_secrets;
+                self.state.set_notification(
+                    if self.state.reveal_secrets {
+                        "Secret values revealed"
+                    } else {
+                        "Secret values masked"
+                    }
+                    .to_string(),
+                );
+                Ok(ModuleAction::None)
+            }
+
+            // Environment: all variables or only those differing from the parent
+            KeyCode::Char('d') => {
+                self.state.env_diff = !self.state.env_diff;
+                self.state.set_notification(
+                    if self.state.env_diff {
+                        "Environment: diff vs parent"
+                    } else {
+                        "Environment: all variables"
+                    }
+                    .to_string(),
+                );
+                Ok(ModuleAction::None)
+            }
+
+            // Health overview
+            KeyCode::Char('H') => {
+                self.open_health()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Export "Why is this running?" as plain text
+            KeyCode::Char('e') => {
+                self.export_explanation();
+                Ok(ModuleAction::None)
+            }
+
+            // Journal of the selected unit / process
+            KeyCode::Char('J') => {
+                self.open_journal();
+                Ok(ModuleAction::None)
+            }
+
+            // Process actions
+            KeyCode::Char('s') => {
+                self.state.open_signal_picker();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('n') => {
+                self.state.open_renice();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('R') | KeyCode::Char('S') => {
+                let unit = match self
+                    .state
+                    .get_selected_result()
+                    .map(|r| &r.process.supervisor)
+                {
+                    Some(Supervisor::Systemd { unit }) if unit.ends_with(".service") => {
+                        unit.clone()
+                    }
+                    Some(_) => {
+                        self.state
+                            .set_notification("Not supervised by a systemd unit".to_string());
+                        return Ok(ModuleAction::None);
+                    }
+                    None => return Ok(ModuleAction::None),
+                };
+                let action = if key.code == KeyCode::Char('R') {
+                    ProcessAction::RestartUnit(unit)
+                } else {
+                    ProcessAction::StopUnit(unit)
+                };
+                self.state.request_action(action);
+                Ok(ModuleAction::None)
+            }
+
             // Quit (also exits)
             KeyCode::Char('q') => Ok(ModuleAction::Exit),
 
             _ => Ok(ModuleAction::None),
         }
     }
+
+    /// Handle key events in the signal picker
+    fn handle_signal_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc => self.state.cancel_action(),
+            KeyCode::Up | KeyCode::Char('k') => self.state.signal_select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.signal_select_next(),
+            KeyCode::Enter => {
+                let signal = self.state.selected_signal();
+                self.state.request_action(ProcessAction::Signal(signal));
+            }
+            _ => {}
+        }
+
+        Ok(ModuleAction::None)
+    }
+
+    /// Handle key events in the renice prompt
+    fn handle_renice_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc => self.state.cancel_action(),
+            KeyCode::Char(c)
+                if c.is_ascii_digit() || (c == '-' && self.state.renice_input.is_empty()) =>
+            {
+                self.state.renice_input.push(c);
+            }
+            KeyCode::Backspace => {
+                self.state.renice_input.pop();
+            }
+            KeyCode::Enter => match self.state.renice_input.parse::<i32>() {
+                Ok(nice) if (-20..=19).contains(&nice) => {
+                    self.state.request_action(ProcessAction::Renice(nice));
+                }
+                _ => {
+                    self.state
+                        .set_notification("Nice value must be between -20 and 19".to_string());
+                }
+            },
+            _ => {}
+        }
+
+        Ok(ModuleAction::None)
+    }
+
+    /// Handle key events in the confirmation dialog
+    fn handle_confirm_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            // Enter also picks the signal, so only an explicit 'y' confirms
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(pending) = self.state.pending_action.take() {
+                    let msg = match actions::execute_action(&pending) {
+                        Ok(msg) => msg,
+                        Err(e) => format!("Error: {}", e),
+                    };
+                    self.state.cancel_action();
+                    self.state.set_notification(msg);
+                    self.refresh_results();
+                }
+            }
+            _ => {
+                self.state.cancel_action();
+                self.state.set_notification("Cancelled".to_string());
+            }
+        }
+
+        Ok(ModuleAction::None)
+    }
+
+    /// Handle key events in journal mode
+    fn handle_journal_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        let Some(ref mut journal) = self.state.journal else {
+            self.state.close_journal();
+            return Ok(ModuleAction::None);
+        };
+
+        match key.code {
+            KeyCode::Esc => self.state.close_journal(),
+
+            // Scroll (offset counts from the newest line)
+            KeyCode::Up | KeyCode::Char('k') => journal.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => journal.scroll_down(1),
+            KeyCode::PageUp => journal.scroll_up(10),
+            KeyCode::PageDown => journal.scroll_down(10),
+            KeyCode::Home | KeyCode::Char('g') => journal.scroll_up(usize::MAX / 2),
+            KeyCode::End | KeyCode::Char('G') => journal.scroll = 0,
+
+            // Priority filter
+            KeyCode::Char('p') => {
+                journal.cycle_priority();
+                let msg = if journal.max_priority == 7 {
+                    "Priority: all".to_string()
+                } else {
+                    format!(
+                        "Priority: {} and above",
+                        PRIORITY_NAMES[journal.max_priority as usize]
+                    )
+                };
+                self.reload_journal();
+                self.state.set_notification(msg);
+            }
+
+            // Follow mode
+            KeyCode::Char('f') => {
+                journal.follow = !journal.follow;
+                if journal.follow {
+                    journal.scroll = 0;
+                }
+                let msg = if journal.follow {
+                    "Follow on"
+                } else {
+                    "Follow off"
+                };
+                self.state.set_notification(msg.to_string());
+            }
+
+            // Load more history / reload
+            KeyCode::Char('m') => {
+                journal.more_lines();
+                let lines = journal.lines;
+                self.reload_journal();
+                self.state
+                    .set_notification(format!("Loading last {} lines", lines));
+            }
+            KeyCode::Char('r') => self.reload_journal(),
+
+            KeyCode::Char('q') => return Ok(ModuleAction::Exit),
+
+            _ => {}
+        }
+
+        Ok(ModuleAction::None)
+    }
+
+    /// Handle key events in tree mode
+    fn handle_tree_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            // Return to previous mode
+            KeyCode::Esc => {
+                self.state.close_tree();
+                Ok(ModuleAction::None)
+            }
+
+            // Navigate rows
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.tree_select_previous();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.tree_select_next();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.state.tree_select_first();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.state.tree_select_last();
+                Ok(ModuleAction::None)
+            }
+
+            // Collapse / expand
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.state.tree_collapse();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.state.tree_expand();
+                Ok(ModuleAction::None)
+            }
+
+            // Jump to parent / first child
+            KeyCode::Char('p') => {
+                self.state.tree_jump_parent();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Char('c') => {
+                self.state.tree_jump_child();
+                Ok(ModuleAction::None)
+            }
+
+            // Show full details for the selected process
+            KeyCode::Enter => {
+                if let Some(pid) = self.state.selected_tree_pid() {
+                    self.state.close_tree();
+                    self.state.query_input = pid.to_string();
+                    self.execute_query()?;
+                }
+                Ok(ModuleAction::None)
+            }
+
+            KeyCode::Char('q') => Ok(ModuleAction::Exit),
+
+            _ => Ok(ModuleAction::None),
+        }
+    }
+
+    /// Handle key events in audit mode
+    fn handle_audit_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            // Return to previous mode
+            KeyCode::Esc => {
+                self.state.close_audit();
+                Ok(ModuleAction::None)
+            }
+
+            // Navigate entries
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.audit_select_previous();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.audit_select_next();
+                Ok(ModuleAction::None)
+            }
+
+            // Re-scan listeners
+            KeyCode::Char('r') => {
+                self.open_audit()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Show full details for the owning process
+            KeyCode::Enter => {
+                let pid = self
+                    .state
+                    .audit_entries
+                    .get(self.state.audit_selected)
+                    .and_then(|entry| entry.process.as_ref())
+                    .map(|process| process.pid);
+                match pid {
+                    Some(pid) => {
+                        self.state.close_audit();
+                        self.state.query_input = pid.to_string();
+                        self.execute_query()?;
+                    }
+                    None => {
+                        self.state
+                            .set_notification("Owner not visible (try sudo)".to_string());
+                    }
+                }
+                Ok(ModuleAction::None)
+            }
+
+            KeyCode::Char('q') => Ok(ModuleAction::Exit),
+
+            _ => Ok(ModuleAction::None),
+        }
+    }
+
+    /// Handle key events in health mode
+    fn handle_health_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            // Return to previous mode
+            KeyCode::Esc => {
+                self.state.close_health();
+                Ok(ModuleAction::None)
+            }
+
+            // Navigate entries
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.health_select_previous();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.health_select_next();
+                Ok(ModuleAction::None)
+            }
+
+            // Re-scan processes
+            KeyCode::Char('r') => {
+                self.open_health()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Show full details (for zombies: the parent that should reap them)
+            KeyCode::Enter | KeyCode::Char('p') => {
+                let pid = self.state.health.as_ref().and_then(|h| {
+                    let entry = h.entries.get(self.state.health_selected)?;
+                    Some(if key.code == KeyCode::Char('p') {
+                        entry.process.ppid
+                    } else {
+                        entry.process.pid
+                    })
+                });
+                if let Some(pid) = pid {
+                    self.state.close_health();
+                    self.state.query_input = pid.to_string();
+                    self.execute_query()?;
+                }
+                Ok(ModuleAction::None)
+            }
+
+            KeyCode::Char('q') => Ok(ModuleAction::Exit),
+
+            _ => Ok(ModuleAction::None),
+        }
+    }
+
+    /// Handle key events in ports mode
+    fn handle_ports_mode(&mut self, key: KeyEvent) -> Result<ModuleAction> {
+        use ratatui::crossterm::event::KeyCode;
+
+        match key.code {
+            // Return to previous mode
+            KeyCode::Esc => {
+                self.state.close_ports();
+                Ok(ModuleAction::None)
+            }
+
+            // Navigate groups
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.ports_select_previous();
+                Ok(ModuleAction::None)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.ports_select_next();
+                Ok(ModuleAction::None)
+            }
+
+            // Re-scan listeners
+            KeyCode::Char('r') => {
+                self.open_ports()?;
+                Ok(ModuleAction::None)
+            }
+
+            // Show every owner of the selected port
+            KeyCode::Enter => {
+                let port = self
+                    .state
+                    .port_groups
+                    .get(self.state.ports_selected)
+                    .map(|group| group.port);
+                if let Some(port) = port {
+                    self.state.close_ports();
+                    self.state.query_input = format!(":{}", port);
+                    self.execute_query()?;
+                }
+                Ok(ModuleAction::None)
+            }
+
+            KeyCode::Char('q') => Ok(ModuleAction::Exit),
+
+            _ => Ok(ModuleAction::None),
+        }
+    }
 }
 
 impl Module for ProcessTracerModule {
@@ -182,6 +988,13 @@ impl Module for ProcessTracerModule {
         self.state.selected_result = 0;
         self.state.scroll_offset = 0;
         self.state.notification = None;
+        self.state.tree = None;
+        self.state.tree_rows.clear();
+        self.state.audit_entries.clear();
+        self.state.port_groups.clear();
+        self.state.health = None;
+        self.state.last_query = None;
+        self.state.watch_mode = false;
 
         Ok(())
     }
@@ -190,12 +1003,35 @@ impl Module for ProcessTracerModule {
         match self.state.input_mode {
             InputMode::Query => self.handle_query_mode(key_event),
             InputMode::ViewResults => self.handle_results_mode(key_event),
+            InputMode::Tree => self.handle_tree_mode(key_event),
+            InputMode::Audit => self.handle_audit_mode(key_event),
+            InputMode::Ports => self.handle_ports_mode(key_event),
+            InputMode::Signal => self.handle_signal_mode(key_event),
+            InputMode::Renice => self.handle_renice_mode(key_event),
+            InputMode::Confirm => self.handle_confirm_mode(key_event),
+            InputMode::Journal => self.handle_journal_mode(key_event),
+            InputMode::Health => self.handle_health_mode(key_event),
         }
     }
 
     fn update(&mut self) -> Result<()> {
-        // Only clear expired notifications (no auto-refresh)
         self.state.clear_expired_notifications();
+
+        // Re-run the current query in watch mode
+        if self.state.watch_due() {
+            self.refresh_results();
+        }
+
+        // Append new journal lines in follow mode
+        if self.state.journal_follow_due()
+            && let Some(ref mut journal) = self.state.journal
+            && let Err(e) = journal.poll()
+        {
+            journal.follow = false;
+            self.state
+                .set_notification(format!("Journal follow stopped: {}", e));
+        }
+
         Ok(())
     }
 
@@ -209,6 +1045,14 @@ impl Module for ProcessTracerModule {
         self.state.query_input.clear();
         self.state.query_results.clear();
         self.state.notification = None;
+        self.state.tree = None;
+        self.state.tree_rows.clear();
+        self.state.audit_entries.clear();
+        self.state.port_groups.clear();
+        self.state.health = None;
+        self.state.watch_mode = false;
+        self.state.pending_action = None;
+        self.state.journal = None;
 
         Ok(())
     }
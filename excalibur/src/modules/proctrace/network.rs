@@ -1,12 +1,17 @@
 use color_eyre::Result;
+use serde::Serialize;
+use std::cell::OnceCell;
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::process::Command;
 
 /// Network protocol
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Protocol {
     Tcp,
     Udp,
+    Raw, // local_port holds the IP protocol number
 }
 
 impl Protocol {
@@ -14,34 +19,52 @@ impl Protocol {
         match self {
             Protocol::Tcp => "TCP",
             Protocol::Udp => "UDP",
+            Protocol::Raw => "RAW",
         }
     }
 }
 
-/// Connection state
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Connection state (kernel TCP states from include/net/tcp_states.h)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConnectionState {
-    Listen,
     Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
     TimeWait,
+    Close,
     CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    NewSynRecv,
     Unknown,
 }
 
 impl ConnectionState {
     pub fn as_str(&self) -> &str {
         match self {
-            ConnectionState::Listen => "LISTEN",
             ConnectionState::Established => "ESTABLISHED",
+            ConnectionState::SynSent => "SYN_SENT",
+            ConnectionState::SynRecv => "SYN_RECV",
+            ConnectionState::FinWait1 => "FIN_WAIT1",
+            ConnectionState::FinWait2 => "FIN_WAIT2",
             ConnectionState::TimeWait => "TIME_WAIT",
+            ConnectionState::Close => "CLOSE",
             ConnectionState::CloseWait => "CLOSE_WAIT",
+            ConnectionState::LastAck => "LAST_ACK",
+            ConnectionState::Listen => "LISTEN",
+            ConnectionState::Closing => "CLOSING",
+            ConnectionState::NewSynRecv => "NEW_SYN_RECV",
             ConnectionState::Unknown => "UNKNOWN",
         }
     }
 }
 
 /// Network binding information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetworkBinding {
     pub protocol: Protocol,
     pub local_addr: IpAddr,
@@ -49,7 +72,164 @@ pub struct NetworkBinding {
     pub remote_addr: Option<IpAddr>,
     pub remote_port: Option<u16>,
     pub state: ConnectionState,
+    pub send_queue: u32, // tx_queue: bytes not yet acked by the peer
+    pub recv_queue: u32, // rx_queue: bytes not yet read by the process
+    pub inode: u64,
+}
+
+/// Connections of one process grouped by state and remote endpoint
+#[derive(Debug, Clone)]
+pub struct ConnectionSummary {
+    pub state: ConnectionState,
+    pub remote: Option<(IpAddr, u16)>,
+    pub count: usize,
+    pub send_queue: u64,
+    pub recv_queue: u64,
+}
+
+impl ConnectionSummary {
+    /// Describe the group, e.g. "42 ESTABLISHED to 10.0.0.5:5432"
+    pub fn describe(&self) -> String {
+        match self.remote {
+            Some((addr, port)) => format!(
+                "{} {} to {}",
+                self.count,
+                self.state.as_str(),
+                format_socket_addr(&addr, port)
+            ),
+            None => format!("{} {}", self.count, self.state.as_str()),
+        }
+    }
+}
+
+/// Unix domain socket type
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnixSocketType {
+    Stream,
+    Datagram,
+    SeqPacket,
+    Unknown,
+}
+
+impl UnixSocketType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            UnixSocketType::Stream => "STREAM",
+            UnixSocketType::Datagram => "DGRAM",
+            UnixSocketType::SeqPacket => "SEQPACKET",
+            UnixSocketType::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Process on the other end of a connected Unix socket
+#[derive(Debug, Clone, Serialize)]
+pub struct UnixPeer {
+    pub pid: u32,
+    pub name: String,
+}
+
+/// Unix domain socket from /proc/net/unix
+#[derive(Debug, Clone, Serialize)]
+pub struct UnixSocket {
+    pub socket_type: UnixSocketType,
+    pub path: Option<String>, // Filesystem path or "@abstract" name
+    pub listening: bool,
+    pub connected: bool,
     pub inode: u64,
+    pub peer_inode: Option<u64>, // resolved by SocketSnapshot::process_unix_sockets only
+    pub peer: Option<UnixPeer>,
+}
+
+/// Parse /proc/net/unix file (peers are not resolved, see SocketSnapshot)
+pub fn parse_unix_sockets() -> Result<Vec<UnixSocket>> {
+    let content = std::fs::read_to_string("/proc/net/unix")?;
+    Ok(parse_unix_table(&content))
+}
+
+/// Parse /proc/net/unix content
+fn parse_unix_table(content: &str) -> Vec<UnixSocket> {
+    let mut sockets = Vec::new();
+
+    // Format: Num RefCount Protocol Flags Type St Inode [Path]
+    for line in content.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            continue;
+        }
+
+        // __SO_ACCEPTCON flag marks listening sockets
+        let flags = u32::from_str_radix(parts[3], 16).unwrap_or(0);
+        let listening = flags & 0x0001_0000 != 0;
+
+        let socket_type = match parts[4] {
+            "0001" => UnixSocketType::Stream,
+            "0002" => UnixSocketType::Datagram,
+            "0005" => UnixSocketType::SeqPacket,
+            _ => UnixSocketType::Unknown,
+        };
+
+        // SS_CONNECTED
+        let connected = parts[5] == "03";
+
+        let inode: u64 = match parts[6].parse() {
+            Ok(inode) => inode,
+            Err(_) => continue,
+        };
+
+        let path = if parts.len() > 7 {
+            Some(parts[7..].join(" "))
+        } else {
+            None
+        };
+
+        sockets.push(UnixSocket {
+            socket_type,
+            path,
+            listening,
+            connected,
+            inode,
+            peer_inode: None,
+            peer: None,
+        });
+    }
+
+    sockets
+}
+
+/// Read Unix socket peer inodes via `ss -x`
+///
+/// /proc/net/unix does not expose peers, so this relies on `ss` (sock_diag).
+/// Returns an empty map when `ss` is unavailable.
+fn read_unix_peers() -> HashMap<u64, u64> {
+    match Command::new("ss").args(["-x", "-a", "-n", "-H"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_unix_peers(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashMap::new(),
+    }
+}
+
+/// Parse `ss -x -a -n -H` output into local inode → peer inode
+fn parse_unix_peers(output: &str) -> HashMap<u64, u64> {
+    let mut peers = HashMap::new();
+
+    // Format: Netid State Recv-Q Send-Q LocalPath LocalInode PeerPath PeerInode
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 8 {
+            continue;
+        }
+
+        let local = parts[parts.len() - 3].parse::<u64>();
+        let peer = parts[parts.len() - 1].parse::<u64>();
+        if let (Ok(local), Ok(peer @ 1..)) = (local, peer) {
+            peers.insert(local, peer);
+        }
+    }
+
+    peers
 }
 
 /// Parse /proc/net/tcp file
@@ -64,6 +244,48 @@ pub fn parse_udp_connections() -> Result<Vec<NetworkBinding>> {
     parse_connections(&content, Protocol::Udp)
 }
 
+/// Parse /proc/net/tcp6 file
+pub fn parse_tcp6_connections() -> Result<Vec<NetworkBinding>> {
+    let content = std::fs::read_to_string("/proc/net/tcp6")?;
+    parse_connections(&content, Protocol::Tcp)
+}
+
+/// Parse /proc/net/udp6 file
+pub fn parse_udp6_connections() -> Result<Vec<NetworkBinding>> {
+    let content = std::fs::read_to_string("/proc/net/udp6")?;
+    parse_connections(&content, Protocol::Udp)
+}
+
+/// Parse /proc/net/raw and /proc/net/raw6 files
+pub fn parse_raw_connections() -> Result<Vec<NetworkBinding>> {
+    let content = std::fs::read_to_string("/proc/net/raw")?;
+    let mut conns = parse_connections(&content, Protocol::Raw)?;
+    if let Ok(content) = std::fs::read_to_string("/proc/net/raw6") {
+        conns.extend(parse_connections(&content, Protocol::Raw)?);
+    }
+    Ok(conns)
+}
+
+/// Parse all TCP/UDP/raw connections (IPv4 and IPv6)
+///
+/// The IPv6 tables are missing when IPv6 is disabled, so they are optional.
+pub fn parse_all_connections() -> Result<Vec<NetworkBinding>> {
+    let mut all_conns = parse_tcp_connections()?;
+    all_conns.extend(parse_udp_connections()?);
+    all_conns.extend(parse_tcp6_connections().unwrap_or_default());
+    all_conns.extend(parse_udp6_connections().unwrap_or_default());
+    all_conns.extend(parse_raw_connections().unwrap_or_default());
+    Ok(all_conns)
+}
+
+/// Format an address/port pair, bracketing IPv6 addresses: "[::1]:8080"
+pub fn format_socket_addr(addr: &IpAddr, port: u16) -> String {
+    match addr {
+        IpAddr::V4(v4) => format!("{}:{}", v4, port),
+        IpAddr::V6(v6) => format!("[{}]:{}", v6, port),
+    }
+}
+
 /// Generic connection parser
 fn parse_connections(content: &str, protocol: Protocol) -> Result<Vec<NetworkBinding>> {
     let mut connections = Vec::new();
@@ -76,17 +298,27 @@ fn parse_connections(content: &str, protocol: Protocol) -> Result<Vec<NetworkBin
         }
 
         // Parse addresses: "0100007F:EBF7" → 127.0.0.1:60407
-        let local = match parse_address_v4(parts[1]) {
+        let local = match parse_address(parts[1]) {
             Ok(addr) => addr,
             Err(_) => continue,
         };
 
-        let remote =
-            parse_address_v4(parts[2]).unwrap_or((IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0));
+        let remote = parse_address(parts[2]).unwrap_or((IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0));
 
         // Parse state
         let state = parse_state(parts[3]);
 
+        // Parse queues: "00000000:00000000" (tx_queue:rx_queue, hex)
+        let (send_queue, recv_queue) = parts[4]
+            .split_once(':')
+            .map(|(tx, rx)| {
+                (
+                    u32::from_str_radix(tx, 16).unwrap_or(0),
+                    u32::from_str_radix(rx, 16).unwrap_or(0),
+                )
+            })
+            .unwrap_or((0, 0));
+
         // Parse inode
         let inode: u64 = parts[9].parse().unwrap_or(0);
 
@@ -97,6 +329,8 @@ fn parse_connections(content: &str, protocol: Protocol) -> Result<Vec<NetworkBin
             remote_addr: Some(remote.0),
             remote_port: Some(remote.1),
             state,
+            send_queue,
+            recv_queue,
             inode,
         });
     }
@@ -104,6 +338,14 @@ fn parse_connections(content: &str, protocol: Protocol) -> Result<Vec<NetworkBin
     Ok(connections)
 }
 
+/// Parse a hex address of either family, based on its length
+fn parse_address(hex_str: &str) -> Result<(IpAddr, u16)> {
+    match hex_str.split_once(':') {
+        Some((ip, _)) if ip.len() == 32 => parse_address_v6(hex_str),
+        _ => parse_address_v4(hex_str),
+    }
+}
+
 /// Parse hex address format: "0100007F:EBF7" → (127.0.0.1, 60407)
 fn parse_address_v4(hex_str: &str) -> Result<(IpAddr, u16)> {
     let parts: Vec<&str> = hex_str.split(':').collect();
@@ -123,20 +365,116 @@ fn parse_address_v4(hex_str: &str) -> Result<(IpAddr, u16)> {
     Ok((ip, port))
 }
 
+/// Parse IPv6 hex address format:
+/// "00000000000000000000000001000000:1F90" → ([::1], 8080)
+///
+/// The address is four 32-bit words, each in host (little-endian) byte order.
+/// IPv4-mapped addresses (::ffff:a.b.c.d) are normalised to plain IPv4.
+fn parse_address_v6(hex_str: &str) -> Result<(IpAddr, u16)> {
+    let (ip_str, port_str) = hex_str
+        .split_once(':')
+        .ok_or_else(|| color_eyre::eyre::eyre!("Invalid address format"))?;
+    if ip_str.len() != 32 {
+        return Err(color_eyre::eyre::eyre!("Invalid IPv6 address length"));
+    }
+
+    let mut ip_bytes = [0u8; 16];
+    for (idx, chunk) in ip_bytes.chunks_mut(4).enumerate() {
+        let word = u32::from_str_radix(&ip_str[idx * 8..idx * 8 + 8], 16)?;
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+
+    let v6 = Ipv6Addr::from(ip_bytes);
+    let ip = match v6.to_ipv4_mapped() {
+        Some(v4) => IpAddr::V4(v4),
+        None => IpAddr::V6(v6),
+    };
+
+    let port = u16::from_str_radix(port_str, 16)?;
+
+    Ok((ip, port))
+}
+
 /// Parse connection state hex code
 fn parse_state(hex_str: &str) -> ConnectionState {
     match hex_str {
-        "0A" => ConnectionState::Listen,
         "01" => ConnectionState::Established,
+        "02" => ConnectionState::SynSent,
+        "03" => ConnectionState::SynRecv,
+        "04" => ConnectionState::FinWait1,
+        "05" => ConnectionState::FinWait2,
         "06" => ConnectionState::TimeWait,
+        "07" => ConnectionState::Close,
         "08" => ConnectionState::CloseWait,
+        "09" => ConnectionState::LastAck,
+        "0A" => ConnectionState::Listen,
+        "0B" => ConnectionState::Closing,
+        "0C" => ConnectionState::NewSynRecv,
         _ => ConnectionState::Unknown,
     }
 }
 
+/// Summarise TCP connections by state and remote endpoint, largest groups first
+///
+/// Listening sockets are skipped; they are not connections.
+pub fn summarize_connections(bindings: &[NetworkBinding]) -> Vec<ConnectionSummary> {
+    let mut groups: HashMap<(ConnectionState, Option<(IpAddr, u16)>), ConnectionSummary> =
+        HashMap::new();
+
+    for binding in bindings {
+        if binding.protocol != Protocol::Tcp || binding.state == ConnectionState::Listen {
+            continue;
+        }
+
+        let remote = match (binding.remote_addr, binding.remote_port) {
+            (Some(addr), Some(port)) if !addr.is_unspecified() => Some((addr, port)),
+            _ => None,
+        };
+
+        let entry = groups
+            .entry((binding.state, remote))
+            .or_insert(ConnectionSummary {
+                state: binding.state,
+                remote,
+                count: 0,
+                send_queue: 0,
+                recv_queue: 0,
+            });
+        entry.count += 1;
+        entry.send_queue += binding.send_queue as u64;
+        entry.recv_queue += binding.recv_queue as u64;
+    }
+
+    let mut summaries: Vec<ConnectionSummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.state.cmp(&b.state))
+            .then(a.remote.cmp(&b.remote))
+    });
+    summaries
+}
+
+/// Count TCP connections per state (excluding listeners), most common first
+pub fn count_connection_states(bindings: &[NetworkBinding]) -> Vec<(ConnectionState, usize)> {
+    let mut counts: HashMap<ConnectionState, usize> = HashMap::new();
+    for binding in bindings {
+        if binding.protocol == Protocol::Tcp && binding.state != ConnectionState::Listen {
+            *counts.entry(binding.state).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
 /// Map network connections to PIDs by matching socket inodes
-pub fn map_connections_to_pids(connections: &[NetworkBinding]) -> Result<HashMap<u64, u32>> {
-    let mut inode_to_pid = HashMap::new();
+///
+/// A socket inode can be held by several processes (inherited by pre-fork
+/// workers or shared via SO_REUSEPORT), so every holder is recorded.
+pub fn map_connections_to_pids() -> Result<HashMap<u64, Vec<u32>>> {
+    let mut inode_to_pid: HashMap<u64, Vec<u32>> = HashMap::new();
 
     // Get all process PIDs
     let proc_dir = std::fs::read_dir("/proc")?;
@@ -159,7 +497,10 @@ pub fn map_connections_to_pids(connections: &[NetworkBinding]) -> Result<HashMap
                         if let Some(stripped) = target_str.strip_prefix("socket:[") {
                             if let Some(inode_str) = stripped.strip_suffix(']') {
                                 if let Ok(inode) = inode_str.parse::<u64>() {
-                                    inode_to_pid.insert(inode, pid);
+                                    let holders = inode_to_pid.entry(inode).or_default();
+                                    if !holders.contains(&pid) {
+                                        holders.push(pid);
+                                    }
                                 }
                             }
                         }
@@ -172,49 +513,368 @@ pub fn map_connections_to_pids(connections: &[NetworkBinding]) -> Result<HashMap
     Ok(inode_to_pid)
 }
 
-/// Find process listening on specific port
-pub fn find_process_by_port(port: u16) -> Result<Option<u32>> {
-    // Parse all connections
-    let mut all_conns = parse_tcp_connections()?;
-    all_conns.extend(parse_udp_connections()?);
-
-    // Filter for listening connections on this port
-    let listening: Vec<_> = all_conns
-        .iter()
-        .filter(|c| c.local_port == port && c.state == ConnectionState::Listen)
-        .collect();
+/// Set of ports from a query: single ports and inclusive ranges (":80,443,8000-8100")
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortSpec {
+    pub ranges: Vec<(u16, u16)>,
+}
 
-    if listening.is_empty() {
-        return Ok(None);
+impl PortSpec {
+    /// Check whether a port is part of this spec
+    pub fn contains(&self, port: u16) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| start <= port && port <= end)
     }
+}
 
-    // Build inode → PID mapping
-    let inode_map = map_connections_to_pids(&all_conns)?;
+impl std::fmt::Display for PortSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|&(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{}-{}", start, end)
+                }
+            })
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
 
-    // Find first match
-    for conn in listening {
-        if let Some(&pid) = inode_map.get(&conn.inode) {
-            return Ok(Some(pid));
+/// Check whether a binding is a listener: a TCP LISTEN socket or a bound,
+/// unconnected UDP socket (reported as TCP_CLOSE)
+pub fn is_listening(binding: &NetworkBinding) -> bool {
+    match binding.protocol {
+        Protocol::Tcp => binding.state == ConnectionState::Listen,
+        Protocol::Udp => {
+            binding.state == ConnectionState::Close
+                && binding.remote_addr.is_none_or(|addr| addr.is_unspecified())
         }
+        Protocol::Raw => false,
     }
+}
 
-    Ok(None)
+/// Find every process listening on any port in the spec
+pub fn find_processes_by_port(ports: &PortSpec) -> Result<Vec<u32>> {
+    let listeners = list_bindings_with_owners(|b| ports.contains(b.local_port) && is_listening(b))?;
+
+    let mut pids: Vec<u32> = listeners.into_iter().flat_map(|(_, pids)| pids).collect();
+    pids.sort_unstable();
+    pids.dedup();
+
+    Ok(pids)
 }
 
-/// Get all network bindings for a specific process
-pub fn get_process_bindings(pid: u32) -> Result<Vec<NetworkBinding>> {
-    // Parse all connections
-    let mut all_conns = parse_tcp_connections()?;
-    all_conns.extend(parse_udp_connections()?);
+/// Socket tables and the inode → PID map, read at most once per query
+///
+/// Building a result needs the process's sockets; reading /proc/net/* and
+/// walking every /proc/*/fd once per result would be quadratic, so the
+/// tables are loaded on first use and shared by all results of a query.
+#[derive(Debug, Default)]
+pub struct SocketSnapshot {
+    connections: OnceCell<Vec<NetworkBinding>>,
+    unix_sockets: OnceCell<Vec<UnixSocket>>,
+    owners: OnceCell<HashMap<u64, Vec<u32>>>,
+    unix_peers: OnceCell<HashMap<u64, u64>>, // needs `ss`, loaded only for connected sockets
+}
 
-    // Build inode → PID mapping
-    let inode_map = map_connections_to_pids(&all_conns)?;
+impl SocketSnapshot {
+    fn connections(&self) -> &[NetworkBinding] {
+        self.connections
+            .get_or_init(|| parse_all_connections().unwrap_or_default())
+    }
 
-    // Filter connections for this PID
-    let bindings: Vec<_> = all_conns
+    fn unix_sockets(&self) -> &[UnixSocket] {
+        self.unix_sockets
+            .get_or_init(|| parse_unix_sockets().unwrap_or_default())
+    }
+
+    fn owners(&self) -> &HashMap<u64, Vec<u32>> {
+        self.owners
+            .get_or_init(|| map_connections_to_pids().unwrap_or_default())
+    }
+
+    fn unix_peers(&self) -> &HashMap<u64, u64> {
+        self.unix_peers.get_or_init(read_unix_peers)
+    }
+
+    fn is_held_by(&self, inode: u64, pid: u32) -> bool {
+        self.owners()
+            .get(&inode)
+            .is_some_and(|pids| pids.contains(&pid))
+    }
+
+    /// All network bindings of a process
+    pub fn process_bindings(&self, pid: u32) -> Vec<NetworkBinding> {
+        self.connections()
+            .iter()
+            .filter(|conn| self.is_held_by(conn.inode, pid))
+            .cloned()
+            .collect()
+    }
+
+    /// All Unix sockets of a process, with connected peers resolved
+    pub fn process_unix_sockets(&self, pid: u32) -> Vec<UnixSocket> {
+        let mut sockets: Vec<UnixSocket> = self
+            .unix_sockets()
+            .iter()
+            .filter(|sock| self.is_held_by(sock.inode, pid))
+            .cloned()
+            .collect();
+
+        for sock in sockets.iter_mut().filter(|sock| sock.connected) {
+            sock.peer_inode = self.unix_peers().get(&sock.inode).copied();
+            let peer_pid = sock
+                .peer_inode
+                .and_then(|inode| self.owners().get(&inode))
+                .and_then(|pids| pids.first().copied());
+            if let Some(peer_pid) = peer_pid {
+                let name = std::fs::read_to_string(format!("/proc/{}/comm", peer_pid))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|_| "?".to_string());
+                sock.peer = Some(UnixPeer {
+                    pid: peer_pid,
+                    name,
+                });
+            }
+        }
+
+        sockets
+    }
+}
+
+/// Find processes owning Unix sockets whose path matches
+///
+/// With `exact`, the path must match exactly (abstract sockets may omit the
+/// leading '@'); otherwise a case-insensitive substring match is used.
+pub fn find_processes_by_unix_socket(pattern: &str, exact: bool) -> Result<Vec<u32>> {
+    let pattern_lower = pattern.to_lowercase();
+
+    let matching: Vec<UnixSocket> = parse_unix_sockets()?
         .into_iter()
-        .filter(|conn| inode_map.get(&conn.inode) == Some(&pid))
+        .filter(|sock| match sock.path {
+            Some(ref path) if exact => path == pattern || path.strip_prefix('@') == Some(pattern),
+            Some(ref path) => path.to_lowercase().contains(&pattern_lower),
+            None => false,
+        })
         .collect();
 
-    Ok(bindings)
+    if matching.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let inode_map = map_connections_to_pids()?;
+
+    let mut pids: Vec<u32> = matching
+        .iter()
+        .filter_map(|sock| inode_map.get(&sock.inode))
+        .flatten()
+        .copied()
+        .collect();
+    pids.sort_unstable();
+    pids.dedup();
+
+    Ok(pids)
+}
+
+/// List all bindings matching a filter system-wide, with their owning PIDs
+///
+/// The PID list is empty when the owning process is not visible (usually a
+/// permission issue for other users' processes).
+pub fn list_bindings_with_owners(
+    filter: impl Fn(&NetworkBinding) -> bool,
+) -> Result<Vec<(NetworkBinding, Vec<u32>)>> {
+    let bindings: Vec<NetworkBinding> = parse_all_connections()?
+        .into_iter()
+        .filter(|b| filter(b))
+        .collect();
+
+    let inode_map = map_connections_to_pids()?;
+
+    Ok(bindings
+        .into_iter()
+        .map(|b| {
+            let pids = inode_map.get(&b.inode).cloned().unwrap_or_default();
+            (b, pids)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROC_NET_UNIX: &str = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 19000 @/tmp/.X11-unix/X0
+0000000000000000: 00000002 00000000 00010000 0001 01 12345 /run/dbus/system_bus_socket
+0000000000000000: 00000003 00000000 00000000 0001 03 23455
+0000000000000000: 00000003 00000000 00000000 0001 03 23456 /run/user/1000/my socket
+0000000000000000: 00000002 00000000 00000000 0002 01 1200 /run/systemd/notify
+0000000000000000: 00000002 00000000 00000000 0005 01 1300 @seqpacket
+";
+
+    const SS_OUTPUT: &str = "\
+u_str LISTEN 0      4096   @/tmp/.X11-unix/X0 19000 * 0
+u_str LISTEN 0      4096   /run/dbus/system_bus_socket 12345 * 0
+u_str ESTAB  0      0      * 23455 * 23456
+u_str ESTAB  0      0      /run/user/1000/my socket 23456 * 23455
+u_dgr UNCONN 0      0      /run/systemd/notify 1200 * 0
+";
+
+    #[test]
+    fn unix_sockets_from_proc_net_unix() {
+        let sockets = parse_unix_table(PROC_NET_UNIX);
+        assert_eq!(sockets.len(), 6);
+
+        let summary: Vec<_> = sockets
+            .iter()
+            .map(|s| {
+                (
+                    s.inode,
+                    s.path.as_deref(),
+                    s.socket_type,
+                    s.listening,
+                    s.connected,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    19000,
+                    Some("@/tmp/.X11-unix/X0"),
+                    UnixSocketType::Stream,
+                    true,
+                    false
+                ),
+                (
+                    12345,
+                    Some("/run/dbus/system_bus_socket"),
+                    UnixSocketType::Stream,
+                    true,
+                    false
+                ),
+                (23455, None, UnixSocketType::Stream, false, true),
+                (
+                    23456,
+                    Some("/run/user/1000/my socket"),
+                    UnixSocketType::Stream,
+                    false,
+                    true
+                ),
+                (
+                    1200,
+                    Some("/run/systemd/notify"),
+                    UnixSocketType::Datagram,
+                    false,
+                    false
+                ),
+                (
+                    1300,
+                    Some("@seqpacket"),
+                    UnixSocketType::SeqPacket,
+                    false,
+                    false
+                ),
+            ]
+        );
+        assert!(sockets.iter().all(|s| s.peer_inode.is_none()));
+    }
+
+    #[test]
+    fn unix_peers_from_ss_output() {
+        let peers = parse_unix_peers(SS_OUTPUT);
+        // Both ends of the connected pair, nothing for unconnected sockets
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers.get(&23455), Some(&23456));
+        assert_eq!(peers.get(&23456), Some(&23455));
+        assert_eq!(peers.get(&19000), None);
+        assert_eq!(peers.get(&1200), None);
+    }
+
+    #[test]
+    fn malformed_unix_lines_are_skipped() {
+        let table = "Num RefCount Protocol Flags Type St Inode Path\n\
+                     0000000000000000: 00000002 00000000\n\
+                     0000000000000000: 00000002 00000000 00000000 0001 01 x /tmp/s\n";
+        assert!(parse_unix_table(table).is_empty());
+        assert!(parse_unix_peers("u_str ESTAB 0 0 * x * 23456\n").is_empty());
+        assert!(parse_unix_peers("").is_empty());
+    }
+
+    fn v6(hex: &str) -> (IpAddr, u16) {
+        parse_address_v6(hex).unwrap()
+    }
+
+    #[test]
+    fn ipv6_addresses_from_proc_net_tcp6() {
+        // Each 32-bit word is stored in host (little-endian) byte order
+        assert_eq!(
+            v6("00000000000000000000000000000000:0016"),
+            (IpAddr::V6(Ipv6Addr::UNSPECIFIED), 22)
+        );
+        assert_eq!(
+            v6("00000000000000000000000001000000:0277"),
+            (IpAddr::V6(Ipv6Addr::LOCALHOST), 631)
+        );
+        assert_eq!(
+            v6("B80D0120000000000000000001000000:01BB"),
+            (IpAddr::V6("2001:db8::1".parse().unwrap()), 443)
+        );
+        assert_eq!(
+            v6("5014002A2B080140000000000E200000:C350"),
+            (
+                IpAddr::V6("2a00:1450:4001:82b::200e".parse().unwrap()),
+                50000
+            )
+        );
+    }
+
+    #[test]
+    fn ipv4_mapped_addresses_are_reported_as_ipv4() {
+        assert_eq!(
+            v6("0000000000000000FFFF00000100007F:1F90"),
+            (IpAddr::V4(Ipv4Addr::LOCALHOST), 8080)
+        );
+        assert_eq!(
+            parse_address_v4("0100007F:1F90").unwrap(),
+            (IpAddr::V4(Ipv4Addr::LOCALHOST), 8080)
+        );
+    }
+
+    #[test]
+    fn malformed_ipv6_addresses_are_rejected() {
+        assert!(parse_address_v6("0000000000000000:0016").is_err());
+        assert!(parse_address_v6("00000000000000000000000000000000").is_err());
+        assert!(parse_address_v6("0000000000000000000000000000000G:0016").is_err());
+    }
+
+    #[test]
+    fn tcp_states_decode_kernel_codes() {
+        let states = [
+            ("01", ConnectionState::Established),
+            ("02", ConnectionState::SynSent),
+            ("03", ConnectionState::SynRecv),
+            ("04", ConnectionState::FinWait1),
+            ("05", ConnectionState::FinWait2),
+            ("06", ConnectionState::TimeWait),
+            ("07", ConnectionState::Close),
+            ("08", ConnectionState::CloseWait),
+            ("09", ConnectionState::LastAck),
+            ("0A", ConnectionState::Listen),
+            ("0B", ConnectionState::Closing),
+            ("0C", ConnectionState::NewSynRecv),
+        ];
+        for (code, state) in states {
+            assert_eq!(parse_state(code), state, "state {}", code);
+        }
+        assert_eq!(parse_state("00"), ConnectionState::Unknown);
+        assert_eq!(parse_state("0D"), ConnectionState::Unknown);
+    }
 }
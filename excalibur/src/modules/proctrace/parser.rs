@@ -0,0 +1,630 @@
+use super::network::PortSpec;
+use super::query::{Comparison, QueryType};
+use std::fmt;
+
+/// Query parse error with the character position of the mistake
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParseError {
+    pub position: usize, // Character index into the query input
+    pub message: String,
+}
+
+impl QueryParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+/// Lexer token
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+    Literal(String), // word starting with a quote: always a name, never an operator
+}
+
+/// Token with its starting character position
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    start: usize,
+}
+
+/// Split query input into tokens
+///
+/// Words run until whitespace or a parenthesis. Double quotes group text
+/// containing spaces (`cmd:"manage.py runserver"`); a word that starts with
+/// a quote is a literal name (`"AND"`, `"12"`). `!` at the start of a word is
+/// a NOT operator.
+fn tokenize(input: &str) -> Result<Vec<Spanned>, QueryParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        match c {
+            '(' => {
+                tokens.push(Spanned {
+                    token: Token::LParen,
+                    start,
+                });
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Spanned {
+                    token: Token::RParen,
+                    start,
+                });
+                i += 1;
+            }
+            '!' => {
+                tokens.push(Spanned {
+                    token: Token::Not,
+                    start,
+                });
+                i += 1;
+            }
+            '&' | '|' => {
+                if chars.get(i + 1) != Some(&c) {
+                    return Err(QueryParseError::new(
+                        start,
+                        format!("Expected '{}{}'", c, c),
+                    ));
+                }
+                let token = if c == '&' { Token::And } else { Token::Or };
+                tokens.push(Spanned { token, start });
+                i += 2;
+            }
+            _ => {
+                let literal = c == '"';
+                let mut word = String::new();
+                while i < chars.len() {
+                    let c = chars[i];
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    if c == '"' {
+                        let quote_start = i;
+                        i += 1;
+                        while i < chars.len() && chars[i] != '"' {
+                            word.push(chars[i]);
+                            i += 1;
+                        }
+                        if i >= chars.len() {
+                            return Err(QueryParseError::new(quote_start, "Unterminated quote"));
+                        }
+                    } else {
+                        word.push(c);
+                    }
+                    i += 1;
+                }
+
+                let token = match word.as_str() {
+                    _ if literal => Token::Literal(word),
+                    "AND" | "and" => Token::And,
+                    "OR" | "or" => Token::Or,
+                    "NOT" | "not" => Token::Not,
+                    _ => Token::Word(word),
+                };
+                tokens.push(Spanned { token, start });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over tokens
+///
+/// Grammar (AND binds tighter than OR, adjacent terms are implicitly ANDed):
+///   expr    := and ( OR and )*
+///   and     := unary ( [AND] unary )*
+///   unary   := NOT unary | primary
+///   primary := '(' expr ')' | term
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+    end: usize, // Character length of the input (for end-of-input errors)
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Spanned> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<QueryType, QueryParseError> {
+        let mut left = self.parse_and()?;
+        while matches!(self.peek(), Some(t) if t.token == Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = QueryType::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<QueryType, QueryParseError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek().map(|t| &t.token) {
+                Some(Token::And) => {
+                    self.next();
+                }
+                // Implicit AND between adjacent terms
+                Some(Token::Word(_) | Token::Literal(_) | Token::Not | Token::LParen) => {}
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = QueryType::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryType, QueryParseError> {
+        if matches!(self.peek(), Some(t) if t.token == Token::Not) {
+            self.next();
+            let inner = self.parse_unary()?;
+            return Ok(QueryType::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<QueryType, QueryParseError> {
+        let Some(spanned) = self.next() else {
+            return Err(QueryParseError::new(self.end, "Expected a query term"));
+        };
+
+        match spanned.token {
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Spanned {
+                        token: Token::RParen,
+                        ..
+                    }) => Ok(inner),
+                    Some(other) => Err(QueryParseError::new(other.start, "Expected ')'")),
+                    None => Err(QueryParseError::new(
+                        spanned.start,
+                        "Unclosed '(' (missing ')')",
+                    )),
+                }
+            }
+            Token::Word(word) => parse_term(&word, spanned.start),
+            Token::Literal(word) => Ok(QueryType::ByName(word)),
+            Token::RParen => Err(QueryParseError::new(spanned.start, "Unexpected ')'")),
+            Token::And | Token::Or => Err(QueryParseError::new(
+                spanned.start,
+                "Operator is missing its left-hand term",
+            )),
+            Token::Not => unreachable!("NOT is handled by parse_unary"),
+        }
+    }
+}
+
+/// Parse a full query expression
+pub fn parse_query(input: &str) -> Result<QueryType, QueryParseError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(QueryParseError::new(0, "Empty query"));
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end: input.chars().count(),
+    };
+    let query = parser.parse_or()?;
+
+    if let Some(extra) = parser.peek() {
+        let message = match extra.token {
+            Token::RParen => "Unexpected ')'",
+            _ => "Unexpected token",
+        };
+        return Err(QueryParseError::new(extra.start, message));
+    }
+
+    Ok(query)
+}
+
+/// Field names recognised before a ':'
+const FIELDS: &[&str] = &[
+    "name",
+    "pid",
+    "port",
+    "user",
+    "cmd",
+    "cwd",
+    "env",
+    "unit",
+    "container",
+    "warn",
+    "file",
+    "state",
+    "unix",
+];
+
+/// Parse a single term (a word without operators)
+fn parse_term(word: &str, start: usize) -> Result<QueryType, QueryParseError> {
+    // Bare PID
+    if let Ok(pid) = word.parse::<u32>() {
+        return Ok(QueryType::ByPid(pid));
+    }
+
+    // Port (":8080")
+    if let Some(port_str) = word.strip_prefix(':') {
+        return parse_port(port_str, start + 1);
+    }
+
+    // Unix socket, exact path ("@/run/docker.sock")
+    if let Some(path) = word.strip_prefix('@') {
+        if path.is_empty() {
+            return Err(QueryParseError::new(start + 1, "Expected a socket path"));
+        }
+        return Ok(QueryType::ByUnixSocket {
+            path: path.to_string(),
+            exact: true,
+        });
+    }
+
+    // Numeric comparisons ("cpu>50", "mem>=1G", "uptime>7d")
+    if let Some(op_idx) = word.find(['>', '<', '=']) {
+        let field = &word[..op_idx];
+        if matches!(field, "cpu" | "mem" | "memory" | "uptime") {
+            return parse_comparison(field, &word[op_idx..], start + field.chars().count());
+        }
+    }
+
+    // Field matches ("user:root"); other words with ':' are names ("kworker/0:1")
+    if let Some((field, value)) = word.split_once(':')
+        && FIELDS.contains(&field)
+    {
+        let value_start = start + field.chars().count() + 1;
+        if value.is_empty() {
+            return Err(QueryParseError::new(
+                value_start,
+                format!("Expected a value after '{}:'", field),
+            ));
+        }
+        let value = value.to_string();
+
+        return match field {
+            "name" => Ok(QueryType::ByName(value)),
+            "pid" => value
+                .parse::<u32>()
+                .map(QueryType::ByPid)
+                .map_err(|_| QueryParseError::new(value_start, "Invalid PID")),
+            "port" => parse_port(&value, value_start),
+            "user" => Ok(QueryType::ByUser(value)),
+            "cmd" => Ok(QueryType::ByCmdline(value)),
+            "cwd" => Ok(QueryType::ByCwd(value)),
+            "env" => {
+                let (key, val) = match value.split_once('=') {
+                    Some((k, v)) => (k.to_string(), Some(v.to_string())),
+                    None => (value, None),
+                };
+                if key.is_empty() {
+                    return Err(QueryParseError::new(value_start, "Expected KEY or KEY=VAL"));
+                }
+                Ok(QueryType::ByEnv { key, value: val })
+            }
+            "unit" => Ok(QueryType::ByUnit(value)),
+            "container" => Ok(QueryType::ByContainer(value)),
+            "warn" => Ok(QueryType::ByWarning(value)),
+            "file" => Ok(QueryType::ByFile(value)),
+            "state" => {
+                if !value.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(QueryParseError::new(
+                        value_start,
+                        "Expected state letters (R, S, D, Z, T, ...)",
+                    ));
+                }
+                Ok(QueryType::ByState(value))
+            }
+            "unix" => Ok(QueryType::ByUnixSocket {
+                path: value,
+                exact: false,
+            }),
+            _ => unreachable!("field is one of FIELDS"),
+        };
+    }
+
+    // Processes running deleted/replaced code
+    if word.eq_ignore_ascii_case("needs-restart") {
+        return Ok(QueryType::NeedsRestart);
+    }
+
+    // Default: process name substring
+    Ok(QueryType::ByName(word.to_string()))
+}
+
+/// Parse a port spec: "8080", "80,443", "8000-8100" or a mix
+fn parse_port(port_str: &str, start: usize) -> Result<QueryType, QueryParseError> {
+    let mut ranges = Vec::new();
+    let mut offset = start;
+
+    for part in port_str.split(',') {
+        let parse = |s: &str, pos: usize| {
+            // Port 0 means "any port" to the kernel and never shows up as a binding
+            s.parse::<u16>()
+                .ok()
+                .filter(|&port| port != 0)
+                .ok_or_else(|| QueryParseError::new(pos, "Invalid port (expected 1-65535)"))
+        };
+
+        let range = match part.split_once('-') {
+            Some((lo, hi)) => {
+                let lo_port = parse(lo, offset)?;
+                let hi_port = parse(hi, offset + lo.chars().count() + 1)?;
+                if lo_port > hi_port {
+                    return Err(QueryParseError::new(
+                        offset,
+                        "Port range start exceeds its end",
+                    ));
+                }
+                (lo_port, hi_port)
+            }
+            None => {
+                let port = parse(part, offset)?;
+                (port, port)
+            }
+        };
+
+        ranges.push(range);
+        offset += part.chars().count() + 1;
+    }
+
+    Ok(QueryType::ByPort(PortSpec { ranges }))
+}
+
+/// Parse the operator and value of a numeric comparison
+fn parse_comparison(field: &str, rest: &str, start: usize) -> Result<QueryType, QueryParseError> {
+    let (op, op_len) = if let Some(stripped) = rest.strip_prefix(">=") {
+        (Comparison::GreaterEq, rest.len() - stripped.len())
+    } else if let Some(stripped) = rest.strip_prefix("<=") {
+        (Comparison::LessEq, rest.len() - stripped.len())
+    } else if rest.starts_with('>') {
+        (Comparison::Greater, 1)
+    } else if rest.starts_with('<') {
+        (Comparison::Less, 1)
+    } else {
+        (Comparison::Equal, 1)
+    };
+
+    let value = &rest[op_len..];
+    let value_start = start + op_len;
+    if value.is_empty() {
+        return Err(QueryParseError::new(value_start, "Expected a value"));
+    }
+
+    match field {
+        "cpu" => value
+            .trim_end_matches('%')
+            .parse::<f32>()
+            .map(|v| QueryType::ByCpu(op, v))
+            .map_err(|_| QueryParseError::new(value_start, "Invalid CPU percentage")),
+        "uptime" => parse_duration(value)
+            .map(|v| QueryType::ByUptime(op, v))
+            .ok_or_else(|| {
+                QueryParseError::new(value_start, "Invalid duration (e.g. 30m, 12h, 7d)")
+            }),
+        _ => parse_size(value)
+            .map(|v| QueryType::ByMemory(op, v))
+            .ok_or_else(|| QueryParseError::new(value_start, "Invalid size (e.g. 512M, 1G)")),
+    }
+}
+
+/// Split a value like "1.5G" into its number and unit suffix
+fn split_number(value: &str) -> Option<(f64, String)> {
+    let idx = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let number = value[..idx].parse::<f64>().ok()?;
+    Some((number, value[idx..].to_ascii_lowercase()))
+}
+
+/// Parse a byte size: "1024", "512K", "100M", "1.5G", "1GB"
+fn parse_size(value: &str) -> Option<u64> {
+    let (number, unit) = split_number(value)?;
+    let multiplier: f64 = match unit.trim_end_matches('b').trim_end_matches('i') {
+        "" => 1.0,
+        "k" => 1024.0,
+        "m" => 1024.0 * 1024.0,
+        "g" => 1024.0 * 1024.0 * 1024.0,
+        "t" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+/// Parse a duration in seconds: "45s", "30m", "12h", "7d", "2w" (bare number = seconds)
+fn parse_duration(value: &str) -> Option<u64> {
+    let (number, unit) = split_number(value)?;
+    let multiplier: f64 = match unit.as_str() {
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        "d" => 86400.0,
+        "w" => 7.0 * 86400.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(n: &str) -> QueryType {
+        QueryType::ByName(n.to_string())
+    }
+
+    fn and(a: QueryType, b: QueryType) -> QueryType {
+        QueryType::And(Box::new(a), Box::new(b))
+    }
+
+    fn or(a: QueryType, b: QueryType) -> QueryType {
+        QueryType::Or(Box::new(a), Box::new(b))
+    }
+
+    fn not(a: QueryType) -> QueryType {
+        QueryType::Not(Box::new(a))
+    }
+
+    fn error_at(input: &str) -> usize {
+        parse_query(input).unwrap_err().position
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            parse_query("a || b && c").unwrap(),
+            or(name("a"), and(name("b"), name("c")))
+        );
+        assert_eq!(
+            parse_query("a AND b or c").unwrap(),
+            or(and(name("a"), name("b")), name("c"))
+        );
+    }
+
+    #[test]
+    fn not_binds_tightest() {
+        assert_eq!(parse_query("!a b").unwrap(), and(not(name("a")), name("b")));
+        assert_eq!(
+            parse_query("not a || b").unwrap(),
+            or(not(name("a")), name("b"))
+        );
+        assert_eq!(parse_query("!!a").unwrap(), not(not(name("a"))));
+    }
+
+    #[test]
+    fn adjacent_terms_are_anded_left_to_right() {
+        assert_eq!(
+            parse_query("a b c").unwrap(),
+            and(and(name("a"), name("b")), name("c"))
+        );
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        assert_eq!(
+            parse_query("(a || b) c").unwrap(),
+            and(or(name("a"), name("b")), name("c"))
+        );
+        assert_eq!(
+            parse_query("!(a && b)").unwrap(),
+            not(and(name("a"), name("b")))
+        );
+    }
+
+    #[test]
+    fn quotes_group_spaces_and_operators() {
+        assert_eq!(
+            parse_query(r#"cmd:"manage.py runserver""#).unwrap(),
+            QueryType::ByCmdline("manage.py runserver".to_string())
+        );
+        assert_eq!(parse_query(r#""a || b""#).unwrap(), name("a || b"));
+        assert_eq!(
+            parse_query(r#"cmd:"(x)" || y"#).unwrap(),
+            or(QueryType::ByCmdline("(x)".to_string()), name("y"))
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_names() {
+        assert_eq!(parse_query("kworker/0:1").unwrap(), name("kworker/0:1"));
+        assert_eq!(
+            parse_query("foo:bar || user:root").unwrap(),
+            or(name("foo:bar"), QueryType::ByUser("root".to_string()))
+        );
+    }
+
+    #[test]
+    fn quoted_words_are_literal_names() {
+        assert_eq!(
+            parse_query(r#"a "AND" b"#).unwrap(),
+            and(and(name("a"), name("AND")), name("b"))
+        );
+        assert_eq!(parse_query(r#""12""#).unwrap(), name("12"));
+        assert_eq!(parse_query(r#""not""#).unwrap(), name("not"));
+        assert_eq!(parse_query(r#""user:root""#).unwrap(), name("user:root"));
+        assert_eq!(parse_query("12").unwrap(), QueryType::ByPid(12));
+    }
+
+    #[test]
+    fn errors_point_at_the_mistake() {
+        assert_eq!(error_at(r#"a cmd:"unterminated"#), 6);
+        assert_eq!(error_at("a & b"), 2);
+        assert_eq!(error_at("(a || b"), 0);
+        assert_eq!(error_at("a)"), 1);
+        assert_eq!(error_at("a ||"), 4);
+        assert_eq!(error_at("&& a"), 0);
+        assert_eq!(error_at("user:"), 5);
+        assert_eq!(error_at("a :80,99999"), 6);
+        assert_eq!(error_at("cpu>lots"), 4);
+        assert_eq!(error_at("   "), 0);
+    }
+
+    #[test]
+    fn port_specs_parse_lists_and_ranges() {
+        let ports = |input| match parse_query(input).unwrap() {
+            QueryType::ByPort(spec) => spec.ranges,
+            other => panic!("not a port query: {:?}", other),
+        };
+        assert_eq!(ports(":80"), [(80, 80)]);
+        assert_eq!(ports("port:1"), [(1, 1)]);
+        assert_eq!(
+            ports(":80,443,8000-8100"),
+            [(80, 80), (443, 443), (8000, 8100)]
+        );
+        assert_eq!(ports(":65535"), [(65535, 65535)]);
+        assert_eq!(ports(":22-22"), [(22, 22)]);
+    }
+
+    #[test]
+    fn invalid_port_specs_are_rejected() {
+        assert_eq!(error_at(":0"), 1);
+        assert_eq!(error_at("port:0"), 5);
+        assert_eq!(error_at(":0-80"), 1);
+        assert_eq!(error_at(":65536"), 1);
+        assert_eq!(error_at(":80-70000"), 4);
+        assert_eq!(error_at(":8100-8000"), 1);
+        assert_eq!(error_at(":80,,443"), 4);
+        assert_eq!(error_at(":80,"), 4);
+        assert_eq!(error_at(":,80"), 1);
+        assert_eq!(error_at(":80-"), 4);
+        assert_eq!(error_at(":http"), 1);
+    }
+
+    #[test]
+    fn error_offsets_count_characters() {
+        assert_eq!(error_at("näme user:"), 10);
+    }
+}
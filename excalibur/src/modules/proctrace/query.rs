@@ -1,35 +1,437 @@
 use super::collector::{
-    ProcessCollector, ProcessInfo, Supervisor, read_environment, read_process,
+    ProcessCollector, ProcessInfo, ProcessWarning, detect_binding_warnings, detect_memory_warnings,
+    detect_stale_warning, format_bytes, is_public_listener, read_environment, read_process,
     read_working_directory,
 };
-use super::network::{NetworkBinding, find_process_by_port, get_process_bindings};
-use super::systemd::{SystemdMetadata, fetch_systemd_metadata};
+use super::container::clear_metadata_cache;
+use super::files::{OpenFile, find_processes_by_file, find_stale_files, get_process_open_files};
+use super::io::{IoRates, IoStats, read_io_stats};
+use super::memory::{MemoryDetail, read_memory_detail};
+use super::network::{
+    NetworkBinding, PortSpec, Protocol, SocketSnapshot, UnixSocket, find_processes_by_port,
+    find_processes_by_unix_socket, is_listening, list_bindings_with_owners,
+};
+use super::security::{SecurityContext, read_security_context};
+use super::supervisor::Supervisor;
+use super::systemd::{SystemdClient, SystemdMetadata};
+use super::threads::{ThreadInfo, read_threads};
+use super::tree::ProcessTree;
+use super::users::user_name;
 use color_eyre::Result;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Comparison operator for numeric query fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Equal,
+}
+
+impl Comparison {
+    /// Apply the comparison: `lhs <op> rhs`
+    pub fn test<T: PartialOrd>(&self, lhs: T, rhs: T) -> bool {
+        match self {
+            Comparison::Greater => lhs > rhs,
+            Comparison::GreaterEq => lhs >= rhs,
+            Comparison::Less => lhs < rhs,
+            Comparison::LessEq => lhs <= rhs,
+            Comparison::Equal => lhs == rhs,
+        }
+    }
+}
 
-/// Query type for finding processes
+/// Query AST for finding processes (see `parser::parse_query`)
 #[derive(Debug, Clone, PartialEq)]
 pub enum QueryType {
-    ByName(String), // Process name substring match
-    ByPid(u32),     // Exact PID
-    ByPort(u16),    // Listening port
+    And(Box<QueryType>, Box<QueryType>),
+    Or(Box<QueryType>, Box<QueryType>),
+    Not(Box<QueryType>),
+    ByName(String),                               // Process name substring match
+    ByPid(u32),                                   // Exact PID
+    ByPort(PortSpec),                             // Listening port(s): ":80,443", ":8000-8100"
+    ByUnixSocket { path: String, exact: bool },   // Unix socket path (exact or substring)
+    ByUser(String),                               // user:alice or user:1000
+    ByCmdline(String),                            // cmd: full command line substring
+    ByCwd(String),                                // cwd: working directory substring
+    ByEnv { key: String, value: Option<String> }, // env:KEY or env:KEY=VAL
+    ByUnit(String),                               // unit: systemd unit substring
+    ByContainer(String),                          // container: ID prefix, container or pod name
+    ByCpu(Comparison, f32),                       // cpu>50
+    ByMemory(Comparison, u64),                    // mem>1G (bytes)
+    ByUptime(Comparison, u64),                    // uptime>7d (seconds)
+    ByWarning(String),                            // warn:root
+    ByFile(String),                               // file:/var/log/app.log (open fd holders)
+    NeedsRestart,                                 // needs-restart: deleted/replaced code in use
+    ByState(String),                              // state:Z, state:DT (any of the letters)
+}
+
+impl QueryType {
+    /// Check whether any term in the expression matches a predicate
+    fn any_term(&self, pred: &dyn Fn(&QueryType) -> bool) -> bool {
+        match self {
+            QueryType::And(a, b) | QueryType::Or(a, b) => a.any_term(pred) || b.any_term(pred),
+            QueryType::Not(inner) => inner.any_term(pred),
+            term => pred(term),
+        }
+    }
+
+    /// Check whether the query filters on CPU% (and so samples every process)
+    pub fn filters_cpu(&self) -> bool {
+        self.any_term(&|t| matches!(t, QueryType::ByCpu(..)))
+    }
+}
+
+/// System-wide data computed lazily while evaluating an expression
+#[derive(Debug, Default)]
+struct EvalContext {
+    listening_ports: Option<HashMap<u16, HashSet<u32>>>, // port → owning PIDs
+    public_owners: Option<HashSet<u32>>,                 // PIDs with public listeners
+    unix_owners: HashMap<(String, bool), HashSet<u32>>,  // socket pattern → owning PIDs
+    file_holders: HashMap<String, HashSet<u32>>,         // path → PIDs holding it open
+}
+
+impl EvalContext {
+    fn listening_ports(&mut self) -> &HashMap<u16, HashSet<u32>> {
+        self.listening_ports.get_or_insert_with(|| {
+            let mut ports: HashMap<u16, HashSet<u32>> = HashMap::new();
+            let listeners = list_bindings_with_owners(is_listening).unwrap_or_default();
+            for (binding, pids) in listeners {
+                ports.entry(binding.local_port).or_default().extend(pids);
+            }
+            ports
+        })
+    }
+
+    fn public_owners(&mut self) -> &HashSet<u32> {
+        self.public_owners.get_or_insert_with(|| {
+            list_bindings_with_owners(is_public_listener)
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|(_, pids)| pids)
+                .collect()
+        })
+    }
+
+    fn unix_owners(&mut self, path: &str, exact: bool) -> &HashSet<u32> {
+        self.unix_owners
+            .entry((path.to_string(), exact))
+            .or_insert_with(|| {
+                find_processes_by_unix_socket(path, exact)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect()
+            })
+    }
+
+    fn file_holders(&mut self, path: &str) -> &HashSet<u32> {
+        self.file_holders
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                find_processes_by_file(path)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect()
+            })
+    }
+}
+
+/// Evaluate a query expression against a single process
+fn evaluate(query: &QueryType, process: &ProcessInfo, ctx: &mut EvalContext) -> bool {
+    match query {
+        QueryType::And(a, b) => evaluate(a, process, ctx) && evaluate(b, process, ctx),
+        QueryType::Or(a, b) => evaluate(a, process, ctx) || evaluate(b, process, ctx),
+        QueryType::Not(inner) => !evaluate(inner, process, ctx),
+        QueryType::ByName(name) => process.name.to_lowercase().contains(&name.to_lowercase()),
+        QueryType::ByPid(pid) => process.pid == *pid,
+        QueryType::ByPort(ports) => ctx
+            .listening_ports()
+            .iter()
+            .any(|(port, pids)| ports.contains(*port) && pids.contains(&process.pid)),
+        QueryType::ByUnixSocket { path, exact } => {
+            ctx.unix_owners(path, *exact).contains(&process.pid)
+        }
+        QueryType::ByFile(path) => ctx.file_holders(path).contains(&process.pid),
+        QueryType::ByUser(user) => match user.parse::<u32>() {
+            Ok(uid) => process.uids.contains(uid),
+            Err(_) => process
+                .uids
+                .ids()
+                .iter()
+                .any(|&uid| user_name(uid).eq_ignore_ascii_case(user)),
+        },
+        QueryType::ByCmdline(cmd) => process
+            .cmdline
+            .join(" ")
+            .to_lowercase()
+            .contains(&cmd.to_lowercase()),
+        QueryType::ByCwd(cwd) => {
+            read_working_directory(process.pid).is_ok_and(|dir| dir.contains(cwd.as_str()))
+        }
+        QueryType::ByEnv { key, value } => {
+            read_environment(process.pid).is_ok_and(|env| match (env.get(key), value) {
+                (Some(actual), Some(expected)) => actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+        }
+        QueryType::ByUnit(unit) => {
+            let actual = match &process.supervisor {
+                Supervisor::Systemd { unit } | Supervisor::SystemdUser { unit, .. } => unit.clone(),
+                Supervisor::Session(session) => format!("session-{}.scope", session.id),
+                _ => return false,
+            };
+            actual.to_lowercase().contains(&unit.to_lowercase())
+        }
+        QueryType::ByContainer(pattern) => {
+            let pattern = pattern.to_lowercase();
+            let name_matches = |name: &Option<String>| {
+                name.as_ref()
+                    .is_some_and(|n| n.to_lowercase().contains(&pattern))
+            };
+            match &process.supervisor {
+                Supervisor::Container(container) => {
+                    container.id.starts_with(&pattern) || name_matches(&container.name)
+                }
+                Supervisor::Kubernetes { container, pod } => {
+                    container.id.starts_with(&pattern)
+                        || name_matches(&container.name)
+                        || name_matches(&pod.name)
+                }
+                _ => false,
+            }
+        }
+        QueryType::ByCpu(op, value) => op.test(process.cpu_percent, *value),
+        QueryType::ByMemory(op, value) => op.test(process.memory_rss, *value),
+        QueryType::ByUptime(op, value) => op.test(process.uptime_secs(), *value),
+        QueryType::ByWarning(kind) => {
+            let kind = kind.to_lowercase();
+            process.warnings.iter().any(|w| w.kind() == kind)
+                // Binding warnings are only attached once sockets are known
+                || (kind == "public" && ctx.public_owners().contains(&process.pid))
+                || (kind == "stale_binary" && !find_stale_files(process.pid).is_empty())
+                || (matches!(kind.as_str(), "mem_limit" | "oom")
+                    && detect_memory_warnings(&read_memory_detail(process.pid))
+                        .iter()
+                        .any(|w| w.kind() == kind))
+        }
+        QueryType::NeedsRestart => !find_stale_files(process.pid).is_empty(),
+        QueryType::ByState(states) => states
+            .chars()
+            .any(|s| s.eq_ignore_ascii_case(&process.state)),
+    }
+}
+
+/// Metrics that changed since the previous refresh (watch mode)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ChangedMetrics {
+    pub cpu: bool,
+    pub memory: bool,
+    pub network: bool,
+    pub warnings: bool,
+}
+
+impl ChangedMetrics {
+    /// Compare two snapshots of the same process
+    fn between(old: &QueryResult, new: &QueryResult) -> Self {
+        let old_mem = old.process.memory_rss as f64;
+        let new_mem = new.process.memory_rss as f64;
+
+        Self {
+            // Ignore sub-percent jitter
+            cpu: (old.process.cpu_percent - new.process.cpu_percent).abs() >= 0.5,
+            memory: (old_mem - new_mem).abs() > old_mem * 0.01,
+            network: old.network_bindings.len() != new.network_bindings.len(),
+            warnings: old.process.warnings != new.process.warnings,
+        }
+    }
 }
 
 /// Query result with full context
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct QueryResult {
     pub process: ProcessInfo,
-    pub ancestor_chain: Vec<ProcessInfo>, // PID → PPID → ... → init
+    pub ancestor_chain: Vec<ProcessInfo>, // init → ... → PPID → PID
     pub working_directory: Option<String>, // /proc/[pid]/cwd
     pub environment: HashMap<String, String>, // /proc/[pid]/environ
+    pub parent_environment: Option<HashMap<String, String>>, // Environment of parent()
     pub network_bindings: Vec<NetworkBinding>, // Network connections
+    pub unix_sockets: Vec<UnixSocket>,    // Unix domain sockets
+    pub open_files: Vec<OpenFile>,        // Open file descriptors
+    pub security: SecurityContext,        // Limits, namespaces, capabilities
+    pub memory: MemoryDetail,             // PSS/USS/swap, OOM score, cgroup memory
+    pub io: Option<IoStats>,              // /proc/[pid]/io counters
+    pub io_rates: Option<IoRates>,        // Per-second rates since previous refresh
+    pub threads: Vec<ThreadInfo>,         // /proc/[pid]/task
     pub systemd_metadata: Option<SystemdMetadata>, // Systemd unit info
+    pub exited: bool,                     // Process disappeared during watch mode
+    pub changed: ChangedMetrics,          // Changes since previous refresh
+}
+
+impl QueryResult {
+    /// Parent process (the entry before the process itself in the chain)
+    pub fn parent(&self) -> Option<&ProcessInfo> {
+        self.ancestor_chain.iter().rev().nth(1)
+    }
+}
+
+/// Metric the results list is ordered by (largest first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Default, // query order
+    Cpu,
+    Rss,
+    Pss,
+    Uss,
+    Swap,
+    OomScore,
+    CgroupUsage, // fraction of the cgroup memory limit
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 8] = [
+        SortKey::Default,
+        SortKey::Cpu,
+        SortKey::Rss,
+        SortKey::Pss,
+        SortKey::Uss,
+        SortKey::Swap,
+        SortKey::OomScore,
+        SortKey::CgroupUsage,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Default => "query order",
+            SortKey::Cpu => "cpu",
+            SortKey::Rss => "rss",
+            SortKey::Pss => "pss",
+            SortKey::Uss => "uss",
+            SortKey::Swap => "swap",
+            SortKey::OomScore => "oom score",
+            SortKey::CgroupUsage => "cgroup mem %",
+        }
+    }
+
+    /// Next key in the cycle
+    pub fn next(&self) -> SortKey {
+        let idx = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Sort value of a result (missing values sort last)
+    fn value(&self, result: &QueryResult) -> Option<f64> {
+        let memory = &result.memory;
+        match self {
+            SortKey::Default => None,
+            SortKey::Cpu => Some(result.process.cpu_percent as f64),
+            SortKey::Rss => Some(result.process.memory_rss as f64),
+            SortKey::Pss => memory.pss.map(|v| v as f64),
+            SortKey::Uss => memory.uss.map(|v| v as f64),
+            SortKey::Swap => memory.swap.map(|v| v as f64),
+            SortKey::OomScore => memory.oom_score.map(|v| v as f64),
+            SortKey::CgroupUsage => memory.cgroup.as_ref().and_then(|c| c.usage_ratio()),
+        }
+    }
+
+    /// Value shown next to each result when sorting by a metric not in the list row
+    pub fn display_value(&self, result: &QueryResult) -> Option<String> {
+        let memory = &result.memory;
+        match self {
+            SortKey::Pss => memory.pss.map(format_bytes),
+            SortKey::Uss => memory.uss.map(format_bytes),
+            SortKey::Swap => memory.swap.map(format_bytes),
+            SortKey::OomScore => memory.oom_score.map(|s| format!("oom {}", s)),
+            SortKey::CgroupUsage => memory
+                .cgroup
+                .as_ref()
+                .and_then(|c| c.usage_ratio())
+                .map(|r| format!("{:.0}% of limit", r * 100.0)),
+            SortKey::Default | SortKey::Cpu | SortKey::Rss => None,
+        }
+    }
+
+    /// Sort results by this key, largest first (stable, so ties keep query order)
+    pub fn sort(&self, results: &mut [QueryResult]) {
+        if *self == SortKey::Default {
+            return;
+        }
+        results.sort_by(|a, b| {
+            let (a, b) = (self.value(a), self.value(b));
+            b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+}
+
+/// Publicly bound listener with its owner (security audit)
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub binding: NetworkBinding,
+    pub process: Option<ProcessInfo>, // None if the owner is not visible
+}
+
+/// All listeners on one port/protocol (port overview)
+#[derive(Debug, Clone)]
+pub struct PortGroup {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub bindings: Vec<NetworkBinding>, // One per bound address
+    pub processes: Vec<ProcessInfo>,   // Every owning process
+    pub hidden_owners: bool,           // Some sockets have no visible owner
+}
+
+/// Error for a query that ran fine but found no process (with a hint why)
+#[derive(Debug)]
+pub struct NoMatch(pub String);
+
+impl std::fmt::Display for NoMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NoMatch {}
+
+/// Delay between the two samples CPU% is computed from
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Warning kinds counted by the health overview
+pub const HEALTH_KINDS: [&str; 4] = ["zombie", "d_state", "stopped", "orphan"];
+
+/// Process in an unhealthy scheduler state (health overview)
+#[derive(Debug, Clone)]
+pub struct HealthEntry {
+    pub process: ProcessInfo,
+    pub warning: ProcessWarning,
+}
+
+/// System-wide counts of zombie, D-state, stopped and orphaned processes
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub total_processes: usize,
+    pub entries: Vec<HealthEntry>, // grouped by kind, in HEALTH_KINDS order
+}
+
+impl HealthReport {
+    /// Number of entries of a warning kind
+    pub fn count(&self, kind: &str) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.warning.kind() == kind)
+            .count()
+    }
 }
 
 /// Query engine for process analysis
 #[derive(Debug)]
 pub struct QueryEngine {
     collector: ProcessCollector,
+    systemd: SystemdClient,
+    sockets: SocketSnapshot, // socket tables of the current query
 }
 
 impl QueryEngine {
@@ -37,18 +439,157 @@ impl QueryEngine {
     pub fn new() -> Self {
         Self {
             collector: ProcessCollector::new(),
+            systemd: SystemdClient::new(),
+            sockets: SocketSnapshot::default(),
         }
     }
 
     /// Execute a query and return full context
     pub fn execute(&mut self, query: QueryType) -> Result<Vec<QueryResult>> {
-        match query {
+        // Sockets change between queries; read the tables once per query
+        self.sockets = SocketSnapshot::default();
+        // Containers may have been recreated since the last query
+        clear_metadata_cache();
+
+        // Single-term queries take fast paths with specific error messages
+        let mut results = match query {
             QueryType::ByName(name) => self.query_by_name(&name),
             QueryType::ByPid(pid) => self.query_by_pid(pid),
-            QueryType::ByPort(port) => self.query_by_port(port),
+            QueryType::ByPort(ports) => self.query_by_port(&ports),
+            QueryType::ByUnixSocket { path, exact } => self.query_by_unix_socket(&path, exact),
+            QueryType::ByFile(path) => self.query_by_file(&path),
+            expression => self.query_by_expression(&expression),
+        }?;
+
+        self.attach_systemd_metadata(&mut results);
+        Ok(results)
+    }
+
+    /// Fill in systemd metadata for all results with one batched lookup
+    fn attach_systemd_metadata(&mut self, results: &mut [QueryResult]) {
+        // Unit state changes between queries; cache only within one
+        self.systemd.clear_cache();
+
+        let units: Vec<String> = results
+            .iter()
+            .filter_map(|r| match &r.process.supervisor {
+                Supervisor::Systemd { unit } => Some(unit.clone()),
+                _ => None,
+            })
+            .collect();
+        self.systemd.prefetch(&units);
+
+        for result in results.iter_mut() {
+            if let Supervisor::Systemd { unit } = &result.process.supervisor {
+                result.systemd_metadata = self.systemd.get(unit);
+            }
+        }
+    }
+
+    /// Take a CPU baseline if none exists, so the next collect reports real CPU%
+    pub fn sample_cpu(&mut self) -> Result<()> {
+        if !self.collector.has_samples() {
+            self.collector.collect()?;
+            std::thread::sleep(CPU_SAMPLE_INTERVAL);
+        }
+        Ok(())
+    }
+
+    /// Measure CPU% of the matched processes only (one-shot runs)
+    ///
+    /// Cheaper than [`Self::sample_cpu`] when no CPU filter needs a baseline
+    /// of every process: two reads of each result around a short sleep.
+    pub fn measure_cpu(&mut self, results: &mut [QueryResult]) {
+        if results.is_empty() {
+            return;
+        }
+
+        for result in results.iter() {
+            let _ = self.collector.read(result.process.pid);
+        }
+        std::thread::sleep(CPU_SAMPLE_INTERVAL);
+
+        let is_high_cpu = |w: &ProcessWarning| matches!(w, ProcessWarning::HighCpu { .. });
+        for result in results.iter_mut() {
+            let Ok(sample) = self.collector.read(result.process.pid) else {
+                continue;
+            };
+            if sample.start_ticks != result.process.start_ticks {
+                continue; // exited and PID reused in between
+            }
+            let process = &mut result.process;
+            process.cpu_percent = sample.cpu_percent;
+            process.warnings.retain(|w| !is_high_cpu(w));
+            process
+                .warnings
+                .extend(sample.warnings.into_iter().filter(is_high_cpu));
         }
     }
 
+    /// Evaluate a query expression against every process
+    fn query_by_expression(&mut self, query: &QueryType) -> Result<Vec<QueryResult>> {
+        // CPU% needs two samples; take a short baseline if we have none yet
+        if query.filters_cpu() {
+            self.sample_cpu()?;
+        }
+
+        let all_processes = self.collector.collect()?;
+        let mut ctx = EvalContext::default();
+
+        let mut results = Vec::new();
+        for process in all_processes {
+            if evaluate(query, &process, &mut ctx) {
+                results.push(self.build_query_result(process)?);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Re-run a query and merge it with the previous results
+    ///
+    /// Processes that no longer exist are kept and marked as exited; metrics
+    /// that changed since the previous refresh are flagged.
+    pub fn refresh(
+        &mut self,
+        query: QueryType,
+        previous: &[QueryResult],
+    ) -> Result<Vec<QueryResult>> {
+        // Fast paths report "nothing found" as NoMatch; that is an empty list
+        // here, but real failures (permissions, I/O) are passed on
+        let mut results = match self.execute(query) {
+            Ok(results) => results,
+            Err(e) if e.downcast_ref::<NoMatch>().is_some() => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        for result in &mut results {
+            if let Some(old) = previous
+                .iter()
+                .find(|old| old.process.pid == result.process.pid && !old.exited)
+            {
+                result.changed = ChangedMetrics::between(old, result);
+                result.io_rates = match (&old.io, &result.io) {
+                    (Some(old_io), Some(new_io)) => IoRates::between(old_io, new_io),
+                    _ => None,
+                };
+            }
+        }
+
+        for old in previous {
+            let still_listed = results.iter().any(|r| r.process.pid == old.process.pid);
+            let still_alive = std::path::Path::new(&format!("/proc/{}", old.process.pid)).exists();
+            if !still_listed && !still_alive {
+                let mut gone = old.clone();
+                gone.exited = true;
+                gone.changed = ChangedMetrics::default();
+                results.push(gone);
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Query processes by name (substring match)
     fn query_by_name(&mut self, name: &str) -> Result<Vec<QueryResult>> {
         let all_processes = self.collector.collect()?;
@@ -67,35 +608,215 @@ impl QueryEngine {
 
     /// Query process by exact PID
     fn query_by_pid(&mut self, pid: u32) -> Result<Vec<QueryResult>> {
-        // Read single process
-        let process = read_process(pid)?;
+        // Read single process (through the collector to track CPU deltas)
+        let process = match self.collector.read(pid) {
+            Ok(process) => process,
+            Err(_) if !std::path::Path::new(&format!("/proc/{}", pid)).exists() => {
+                return Err(NoMatch(format!("No process with PID {}", pid)).into());
+            }
+            Err(e) => return Err(e),
+        };
         let result = self.build_query_result(process)?;
         Ok(vec![result])
     }
 
-    /// Query process by listening port
-    fn query_by_port(&mut self, port: u16) -> Result<Vec<QueryResult>> {
-        // Find process listening on this port
-        match find_process_by_port(port)? {
-            Some(pid) => {
-                let process = read_process(pid)?;
-                let result = self.build_query_result(process)?;
-                Ok(vec![result])
+    /// Query every process listening on the given port(s)
+    fn query_by_port(&mut self, ports: &PortSpec) -> Result<Vec<QueryResult>> {
+        let pids = find_processes_by_port(ports)?;
+        if pids.is_empty() {
+            // Port query failed - likely a permission issue
+            // Return error with helpful message
+            return Err(NoMatch(format!(
+                "No process found for port {}. \
+                Note: Querying ports used by root processes requires sudo/root privileges.",
+                ports
+            ))
+            .into());
+        }
+
+        let mut results = Vec::new();
+        for pid in pids {
+            // Process may have exited between socket scan and read
+            if let Ok(process) = self.collector.read(pid) {
+                results.push(self.build_query_result(process)?);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Query processes holding a file open
+    fn query_by_file(&mut self, path: &str) -> Result<Vec<QueryResult>> {
+        let pids = find_processes_by_file(path)?;
+        if pids.is_empty() {
+            return Err(NoMatch(format!(
+                "No process has {} open. \
+                Note: File descriptors of other users' processes require sudo/root privileges.",
+                path
+            ))
+            .into());
+        }
+
+        let mut results = Vec::new();
+        for pid in pids {
+            // Process may have exited between fd scan and read
+            if let Ok(process) = self.collector.read(pid) {
+                results.push(self.build_query_result(process)?);
             }
-            None => {
-                // Port query failed - likely a permission issue
-                // Return error with helpful message
-                Err(color_eyre::eyre::eyre!(
-                    "No process found for port {}. \
-                    Note: Querying ports used by root processes requires sudo/root privileges.",
-                    port
-                ))
+        }
+
+        Ok(results)
+    }
+
+    /// Query processes owning a Unix socket
+    fn query_by_unix_socket(&mut self, path: &str, exact: bool) -> Result<Vec<QueryResult>> {
+        let pids = find_processes_by_unix_socket(path, exact)?;
+        if pids.is_empty() {
+            return Err(NoMatch(format!(
+                "No process found for Unix socket {}. \
+                Note: Sockets of other users' processes require sudo/root privileges.",
+                path
+            ))
+            .into());
+        }
+
+        let mut results = Vec::new();
+        for pid in pids {
+            // Process may have exited between socket scan and read
+            if let Ok(process) = self.collector.read(pid) {
+                results.push(self.build_query_result(process)?);
             }
         }
+
+        Ok(results)
+    }
+
+    /// List every publicly bound listener system-wide with its owner
+    pub fn audit_public_listeners(&mut self) -> Result<Vec<AuditEntry>> {
+        let listeners = list_bindings_with_owners(is_public_listener)?;
+
+        // One entry per owner; shared sockets appear once for each holder
+        let mut entries = Vec::new();
+        for (binding, pids) in listeners {
+            if pids.is_empty() {
+                entries.push(AuditEntry {
+                    binding,
+                    process: None,
+                });
+                continue;
+            }
+            for pid in pids {
+                entries.push(AuditEntry {
+                    binding: binding.clone(),
+                    process: read_process(pid).ok(),
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            a.binding
+                .local_port
+                .cmp(&b.binding.local_port)
+                .then(a.binding.protocol.as_str().cmp(b.binding.protocol.as_str()))
+        });
+
+        Ok(entries)
+    }
+
+    /// Find zombie, D-state, stopped and orphaned processes system-wide
+    pub fn health_overview(&mut self) -> Result<HealthReport> {
+        let processes = self.collector.collect()?;
+        let total_processes = processes.len();
+
+        let mut entries: Vec<HealthEntry> = processes
+            .into_iter()
+            .flat_map(|process| {
+                process
+                    .warnings
+                    .iter()
+                    .filter(|w| HEALTH_KINDS.contains(&w.kind()))
+                    .map(|warning| HealthEntry {
+                        warning: warning.clone(),
+                        process: process.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        entries.sort_by_key(|e| {
+            let rank = HEALTH_KINDS.iter().position(|k| *k == e.warning.kind());
+            (rank, e.process.pid)
+        });
+
+        Ok(HealthReport {
+            total_processes,
+            entries,
+        })
+    }
+
+    /// List all listeners system-wide, grouped by port and protocol
+    pub fn port_overview(&mut self) -> Result<Vec<PortGroup>> {
+        let listeners = list_bindings_with_owners(is_listening)?;
+
+        let mut groups: Vec<PortGroup> = Vec::new();
+        for (binding, pids) in listeners {
+            let idx = match groups
+                .iter()
+                .position(|g| g.port == binding.local_port && g.protocol == binding.protocol)
+            {
+                Some(idx) => idx,
+                None => {
+                    groups.push(PortGroup {
+                        port: binding.local_port,
+                        protocol: binding.protocol,
+                        bindings: Vec::new(),
+                        processes: Vec::new(),
+                        hidden_owners: false,
+                    });
+                    groups.len() - 1
+                }
+            };
+
+            let group = &mut groups[idx];
+            if pids.is_empty() {
+                group.hidden_owners = true;
+            }
+            for pid in pids {
+                if group.processes.iter().any(|p| p.pid == pid) {
+                    continue;
+                }
+                if let Ok(process) = read_process(pid) {
+                    group.processes.push(process);
+                }
+            }
+            group.bindings.push(binding);
+        }
+
+        groups.sort_by(|a, b| {
+            a.port
+                .cmp(&b.port)
+                .then(a.protocol.as_str().cmp(b.protocol.as_str()))
+        });
+        for group in &mut groups {
+            group.processes.sort_by_key(|p| p.pid);
+        }
+
+        Ok(groups)
+    }
+
+    /// Build a process tree of all processes, or of the given roots and their descendants
+    pub fn build_tree(&mut self, roots: Option<&[u32]>) -> Result<ProcessTree> {
+        // CPU rollups need two samples; the first open would show 0% everywhere
+        self.sample_cpu()?;
+        let processes = self.collector.collect()?;
+        Ok(match roots {
+            Some(pids) => ProcessTree::build_subtrees(processes, pids),
+            None => ProcessTree::build(processes),
+        })
     }
 
     /// Build complete QueryResult from ProcessInfo
-    fn build_query_result(&mut self, process: ProcessInfo) -> Result<QueryResult> {
+    fn build_query_result(&mut self, mut process: ProcessInfo) -> Result<QueryResult> {
         let pid = process.pid;
 
         // Build ancestor chain
@@ -106,23 +827,57 @@ impl QueryEngine {
 
         // Read environment variables
         let environment = read_environment(pid).unwrap_or_default();
+        let parent_environment = ancestor_chain
+            .iter()
+            .rev()
+            .nth(1)
+            .and_then(|parent| read_environment(parent.pid).ok());
 
         // Get network bindings
-        let network_bindings = get_process_bindings(pid).unwrap_or_default();
+        let network_bindings = self.sockets.process_bindings(pid);
 
-        // Systemd metadata (if supervisor is systemd)
-        let systemd_metadata = match &process.supervisor {
-            Supervisor::Systemd { unit } => fetch_systemd_metadata(unit).ok(),
-            _ => None,
-        };
+        // Binding-dependent warnings (public listeners)
+        process
+            .warnings
+            .extend(detect_binding_warnings(&network_bindings));
+
+        // Deleted/replaced executable or libraries (needs restart)
+        process.warnings.extend(detect_stale_warning(pid));
+
+        // PSS/USS/swap, OOM score and cgroup memory (near-limit / OOM warnings)
+        let memory = read_memory_detail(pid);
+        process.warnings.extend(detect_memory_warnings(&memory));
+
+        // I/O counters and threads
+        let io = read_io_stats(pid);
+        let threads = read_threads(pid);
+
+        // Get Unix domain sockets
+        let unix_sockets = self.sockets.process_unix_sockets(pid);
+
+        // Get open file descriptors
+        let open_files = get_process_open_files(pid).unwrap_or_default();
+
+        // Get limits, namespaces, capabilities and seccomp state
+        let security = read_security_context(pid);
 
         Ok(QueryResult {
             process,
             ancestor_chain,
             working_directory,
             environment,
+            parent_environment,
             network_bindings,
-            systemd_metadata,
+            unix_sockets,
+            open_files,
+            security,
+            memory,
+            io,
+            io_rates: None, // computed between refreshes in refresh()
+            threads,
+            systemd_metadata: None, // attached per batch in execute()
+            exited: false,
+            changed: ChangedMetrics::default(),
         })
     }
 
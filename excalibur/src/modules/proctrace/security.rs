@@ -0,0 +1,286 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Capability names, indexed by bit (see capabilities(7))
+const CAPABILITY_NAMES: [&str; 41] = [
+    "chown",
+    "dac_override",
+    "dac_read_search",
+    "fowner",
+    "fsetid",
+    "kill",
+    "setgid",
+    "setuid",
+    "setpcap",
+    "linux_immutable",
+    "net_bind_service",
+    "net_broadcast",
+    "net_admin",
+    "net_raw",
+    "ipc_lock",
+    "ipc_owner",
+    "sys_module",
+    "sys_rawio",
+    "sys_chroot",
+    "sys_ptrace",
+    "sys_pacct",
+    "sys_admin",
+    "sys_boot",
+    "sys_nice",
+    "sys_resource",
+    "sys_time",
+    "sys_tty_config",
+    "mknod",
+    "lease",
+    "audit_write",
+    "audit_control",
+    "setfcap",
+    "mac_override",
+    "mac_admin",
+    "syslog",
+    "wake_alarm",
+    "block_suspend",
+    "audit_read",
+    "perfmon",
+    "bpf",
+    "checkpoint_restore",
+];
+
+/// Namespace types under /proc/<pid>/ns
+const NAMESPACE_TYPES: [&str; 8] = ["cgroup", "ipc", "mnt", "net", "pid", "time", "user", "uts"];
+
+/// Usage at or above this fraction of the soft limit is highlighted
+const NEAR_LIMIT_RATIO: f64 = 0.8;
+
+/// One row of /proc/<pid>/limits, with current usage where it can be measured
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceLimit {
+    pub name: String,      // "Max open files"
+    pub soft: Option<u64>, // None = unlimited
+    pub hard: Option<u64>,
+    pub unit: String,       // files, bytes, seconds, ...
+    pub usage: Option<u64>, // current usage in the same unit
+}
+
+impl ResourceLimit {
+    /// Check whether usage is close to the soft limit
+    pub fn is_near_limit(&self) -> bool {
+        match (self.usage, self.soft) {
+            (Some(usage), Some(soft)) if soft > 0 => usage as f64 >= soft as f64 * NEAR_LIMIT_RATIO,
+            _ => false,
+        }
+    }
+}
+
+/// Namespace membership, compared with init (PID 1)
+#[derive(Debug, Clone, Serialize)]
+pub struct Namespace {
+    pub kind: &'static str,
+    pub inode: u64,
+    pub differs_from_init: Option<bool>, // None if init's namespaces are not readable
+}
+
+/// seccomp mode from /proc/<pid>/status
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeccompMode {
+    Disabled,
+    Strict,
+    Filter,
+}
+
+impl SeccompMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeccompMode::Disabled => "disabled",
+            SeccompMode::Strict => "strict",
+            SeccompMode::Filter => "filter",
+        }
+    }
+}
+
+/// Security context of a process (limits, namespaces, capabilities, seccomp)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SecurityContext {
+    pub limits: Vec<ResourceLimit>,
+    pub namespaces: Vec<Namespace>,
+    pub cap_effective: Option<u64>,
+    pub cap_permitted: Option<u64>,
+    pub seccomp: Option<SeccompMode>,
+    pub no_new_privs: Option<bool>,
+}
+
+impl SecurityContext {
+    /// Namespaces that matter for "is this isolated?" (net and mnt)
+    pub fn isolated_namespaces(&self) -> Vec<&'static str> {
+        self.namespaces
+            .iter()
+            .filter(|ns| ns.differs_from_init == Some(true) && matches!(ns.kind, "net" | "mnt"))
+            .map(|ns| ns.kind)
+            .collect()
+    }
+}
+
+/// Read the security context of a process
+///
+/// Every part is best-effort: fields stay empty/None when the corresponding
+/// /proc file is not readable (other users' processes without root).
+pub fn read_security_context(pid: u32) -> SecurityContext {
+    let status = read_status(pid);
+    let field = |key: &str| status.get(key).map(String::as_str);
+
+    SecurityContext {
+        limits: read_limits(pid, &status),
+        namespaces: read_namespaces(pid),
+        cap_effective: field("CapEff").and_then(|v| u64::from_str_radix(v, 16).ok()),
+        cap_permitted: field("CapPrm").and_then(|v| u64::from_str_radix(v, 16).ok()),
+        seccomp: field("Seccomp").and_then(|v| match v {
+            "0" => Some(SeccompMode::Disabled),
+            "1" => Some(SeccompMode::Strict),
+            "2" => Some(SeccompMode::Filter),
+            _ => None,
+        }),
+        no_new_privs: field("NoNewPrivs").map(|v| v == "1"),
+    }
+}
+
+/// Read /proc/<pid>/status as key → value
+fn read_status(pid: u32) -> HashMap<String, String> {
+    std::fs::read_to_string(format!("/proc/{}/status", pid))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Parse a "1234 kB" status value into bytes
+fn status_bytes(status: &HashMap<String, String>, key: &str) -> Option<u64> {
+    let kb = status
+        .get(key)?
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+/// Current usage for the limits that can be measured from /proc
+fn limit_usage(pid: u32, name: &str, status: &HashMap<String, String>) -> Option<u64> {
+    match name {
+        "Max open files" => std::fs::read_dir(format!("/proc/{}/fd", pid))
+            .ok()
+            .map(|entries| entries.count() as u64),
+        "Max address space" => status_bytes(status, "VmSize"),
+        "Max resident set" => status_bytes(status, "VmRSS"),
+        "Max stack size" => status_bytes(status, "VmStk"),
+        "Max locked memory" => status_bytes(status, "VmLck"),
+        // SigQ: <queued for the real UID>/<limit>
+        "Max pending signals" => status
+            .get("SigQ")
+            .and_then(|v| v.split('/').next())
+            .and_then(|v| v.parse().ok()),
+        "Max cpu time" => {
+            let stat = procfs::process::Process::new(pid as i32)
+                .ok()?
+                .stat()
+                .ok()?;
+            Some((stat.utime + stat.stime) / procfs::ticks_per_second())
+        }
+        _ => None,
+    }
+}
+
+/// Parse /proc/<pid>/limits
+///
+/// Lines look like "Max open files            1024                 524288               files";
+/// the limit name contains spaces, so it is taken from the fixed-width first column.
+fn read_limits(pid: u32, status: &HashMap<String, String>) -> Vec<ResourceLimit> {
+    let content = std::fs::read_to_string(format!("/proc/{}/limits", pid)).unwrap_or_default();
+    let parse_value = |v: &str| v.parse::<u64>().ok(); // "unlimited" → None
+
+    content
+        .lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            let name = line.get(..26)?.trim().to_string();
+            let mut values = line.get(26..)?.split_whitespace();
+            let soft = parse_value(values.next()?);
+            let hard = parse_value(values.next()?);
+            let unit = values.next().unwrap_or("").to_string();
+            let usage = limit_usage(pid, &name, status);
+            Some(ResourceLimit {
+                name,
+                soft,
+                hard,
+                unit,
+                usage,
+            })
+        })
+        .collect()
+}
+
+/// Read a namespace inode from "net:[4026531840]"
+fn namespace_inode(pid: u32, kind: &str) -> Option<u64> {
+    let target = std::fs::read_link(format!("/proc/{}/ns/{}", pid, kind)).ok()?;
+    target
+        .to_string_lossy()
+        .rsplit_once('[')?
+        .1
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+fn read_namespaces(pid: u32) -> Vec<Namespace> {
+    NAMESPACE_TYPES
+        .iter()
+        .filter_map(|&kind| {
+            let inode = namespace_inode(pid, kind)?;
+            let differs_from_init = namespace_inode(1, kind).map(|init| init != inode);
+            Some(Namespace {
+                kind,
+                inode,
+                differs_from_init,
+            })
+        })
+        .collect()
+}
+
+/// Highest capability known to the running kernel
+fn cap_last_cap() -> u32 {
+    std::fs::read_to_string("/proc/sys/kernel/cap_last_cap")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(CAPABILITY_NAMES.len() as u32 - 1)
+}
+
+fn capability_name(bit: u32) -> String {
+    CAPABILITY_NAMES
+        .get(bit as usize)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("cap_{}", bit))
+}
+
+/// Decode a capability mask into names ("net_bind_service, sys_admin")
+///
+/// Masks with (nearly) every capability set are shown as "all" or
+/// "all except …", which is far more readable for root processes.
+pub fn describe_capabilities(mask: u64) -> String {
+    if mask == 0 {
+        return "none".to_string();
+    }
+
+    let last = cap_last_cap().min(63);
+    let (set, unset): (Vec<u32>, Vec<u32>) = (0..=last).partition(|bit| mask & (1 << bit) != 0);
+
+    if unset.is_empty() {
+        "all".to_string()
+    } else if unset.len() <= 3 && set.len() > unset.len() {
+        let missing: Vec<String> = unset.into_iter().map(capability_name).collect();
+        format!("all except {}", missing.join(", "))
+    } else {
+        let names: Vec<String> = set.into_iter().map(capability_name).collect();
+        names.join(", ")
+    }
+}
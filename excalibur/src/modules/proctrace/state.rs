@@ -1,12 +1,45 @@
-use super::query::{QueryResult, QueryType};
-use color_eyre::Result;
-use std::time::Instant;
+use super::actions::{PendingAction, ProcessAction, Signal};
+use super::export::ExportFormat;
+use super::journal::JournalView;
+use super::parser::{self, QueryParseError};
+use super::query::{AuditEntry, HealthReport, PortGroup, QueryResult, QueryType, SortKey};
+use super::tree::{ProcessTree, TreeRow};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
 /// Input mode for the process tracer
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Query,       // Entering query
     ViewResults, // Browsing results
+    Tree,        // Browsing process tree
+    Audit,       // Browsing publicly bound listeners
+    Ports,       // Browsing listeners grouped by port
+    Signal,      // Picking a signal for the selected process
+    Renice,      // Entering a new nice value
+    Confirm,     // Confirming a process action
+    Journal,     // Browsing journal lines of the selected result
+    Health,      // Browsing zombie/D-state/stopped/orphaned processes
+}
+
+/// Tab shown in the details panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetailsTab {
+    Overview,
+    Io,
+    Threads,
+}
+
+impl DetailsTab {
+    pub const ALL: [DetailsTab; 3] = [DetailsTab::Overview, DetailsTab::Io, DetailsTab::Threads];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DetailsTab::Overview => "Overview",
+            DetailsTab::Io => "I/O",
+            DetailsTab::Threads => "Threads",
+        }
+    }
 }
 
 /// State for the process tracer module (query-driven)
@@ -30,11 +63,86 @@ pub struct ProcessTracerState {
     /// Notification message with timestamp
     pub notification: Option<(String, Instant)>,
 
+    /// Parse error of the last executed query (shown under the input)
+    pub query_error: Option<QueryParseError>,
+
     /// Query history (for up/down arrow navigation)
     pub query_history: Vec<String>,
 
     /// Current position in history
     pub history_index: usize,
+
+    /// Process tree snapshot (tree mode)
+    pub tree: Option<ProcessTree>,
+
+    /// Visible rows of the flattened tree
+    pub tree_rows: Vec<TreeRow>,
+
+    /// PIDs whose subtrees are collapsed
+    pub tree_collapsed: HashSet<u32>,
+
+    /// Selected row in tree mode
+    pub tree_selected: usize,
+
+    /// Mode to return to when leaving tree/audit/ports mode
+    pub return_mode: InputMode,
+
+    /// Publicly bound listeners (audit mode)
+    pub audit_entries: Vec<AuditEntry>,
+
+    /// Selected entry in audit mode
+    pub audit_selected: usize,
+
+    /// Listeners grouped by port (ports mode)
+    pub port_groups: Vec<PortGroup>,
+
+    /// Selected group in ports mode
+    pub ports_selected: usize,
+
+    /// Last successfully executed query (re-run in watch mode)
+    pub last_query: Option<QueryType>,
+
+    /// Watch mode: periodically re-run the current query
+    pub watch_mode: bool,
+
+    /// Watch mode refresh interval
+    pub watch_interval: Duration,
+
+    /// Time of the last refresh
+    pub last_refresh: Instant,
+
+    /// Selected entry in the signal picker
+    pub signal_selected: usize,
+
+    /// Nice value input buffer (renice mode)
+    pub renice_input: String,
+
+    /// Action waiting for confirmation
+    pub pending_action: Option<PendingAction>,
+
+    /// Journal pane (journal mode)
+    pub journal: Option<JournalView>,
+
+    /// Metric the results are ordered by
+    pub sort_key: SortKey,
+
+    /// Tab shown in the details panel
+    pub details_tab: DetailsTab,
+
+    /// Show secret-looking environment values in plain text
+    pub reveal_secrets: bool,
+
+    /// Show only environment variables added/changed relative to the parent
+    pub env_diff: bool,
+
+    /// Format used by the export keys
+    pub export_format: ExportFormat,
+
+    /// System health overview (health mode)
+    pub health: Option<HealthReport>,
+
+    /// Selected entry in health mode
+    pub health_selected: usize,
 }
 
 impl ProcessTracerState {
@@ -46,29 +154,39 @@ impl ProcessTracerState {
             selected_result: 0,
             scroll_offset: 0,
             notification: None,
+            query_error: None,
             query_history: Vec::new(),
             history_index: 0,
+            tree: None,
+            tree_rows: Vec::new(),
+            tree_collapsed: HashSet::new(),
+            tree_selected: 0,
+            return_mode: InputMode::Query,
+            audit_entries: Vec::new(),
+            audit_selected: 0,
+            port_groups: Vec::new(),
+            ports_selected: 0,
+            last_query: None,
+            watch_mode: false,
+            watch_interval: Duration::from_secs(2),
+            last_refresh: Instant::now(),
+            signal_selected: 0,
+            renice_input: String::new(),
+            pending_action: None,
+            journal: None,
+            sort_key: SortKey::Default,
+            details_tab: DetailsTab::Overview,
+            reveal_secrets: false,
+            env_diff: false,
+            export_format: ExportFormat::Markdown,
+            health: None,
+            health_selected: 0,
         }
     }
 
-    /// Parse query input into QueryType
-    pub fn parse_query(&self) -> Result<QueryType> {
-        let input = self.query_input.trim();
-
-        // Try to parse as PID (pure number)
-        if let Ok(pid) = input.parse::<u32>() {
-            return Ok(QueryType::ByPid(pid));
-        }
-
-        // Try to parse as port (":8080" format)
-        if let Some(port_str) = input.strip_prefix(':') {
-            if let Ok(port) = port_str.parse::<u16>() {
-                return Ok(QueryType::ByPort(port));
-            }
-        }
-
-        // Default: treat as process name
-        Ok(QueryType::ByName(input.to_string()))
+    /// Parse query input into a QueryType expression
+    pub fn parse_query(&self) -> Result<QueryType, QueryParseError> {
+        parser::parse_query(self.query_input.trim_end())
     }
 
     /// Add query to history
@@ -163,8 +281,383 @@ impl ProcessTracerState {
         }
     }
 
+    /// Toggle watch mode
+    pub fn toggle_watch(&mut self) {
+        self.watch_mode = !self.watch_mode;
+        self.last_refresh = Instant::now();
+    }
+
+    /// Increase watch interval (max 60s)
+    pub fn increase_watch_interval(&mut self) {
+        let secs = (self.watch_interval.as_secs() + 1).min(60);
+        self.watch_interval = Duration::from_secs(secs);
+    }
+
+    /// Decrease watch interval (min 1s)
+    pub fn decrease_watch_interval(&mut self) {
+        let secs = self.watch_interval.as_secs().saturating_sub(1).max(1);
+        self.watch_interval = Duration::from_secs(secs);
+    }
+
+    /// Check whether a watch refresh is due
+    pub fn watch_due(&self) -> bool {
+        self.watch_mode
+            && self.input_mode == InputMode::ViewResults
+            && self.last_refresh.elapsed() >= self.watch_interval
+    }
+
+    /// Replace results after a refresh, keeping the selected process selected
+    pub fn replace_results(&mut self, results: Vec<QueryResult>) {
+        let selected_pid = self.get_selected_result().map(|r| r.process.pid);
+        self.query_results = results;
+        self.sort_key.sort(&mut self.query_results);
+        self.last_refresh = Instant::now();
+
+        self.selected_result = selected_pid
+            .and_then(|pid| self.query_results.iter().position(|r| r.process.pid == pid))
+            .unwrap_or(0)
+            .min(self.query_results.len().saturating_sub(1));
+    }
+
+    /// Switch to the next details tab
+    pub fn next_details_tab(&mut self) {
+        let idx = DetailsTab::ALL
+            .iter()
+            .position(|t| *t == self.details_tab)
+            .unwrap_or(0);
+        self.details_tab = DetailsTab::ALL[(idx + 1) % DetailsTab::ALL.len()];
+        self.scroll_offset = 0;
+    }
+
+    /// Switch to the next sort key and re-sort, keeping the selected process selected
+    pub fn cycle_sort(&mut self) {
+        self.sort_key = self.sort_key.next();
+        let selected_pid = self.get_selected_result().map(|r| r.process.pid);
+        self.sort_key.sort(&mut self.query_results);
+
+        if let Some(idx) = selected_pid
+            .and_then(|pid| self.query_results.iter().position(|r| r.process.pid == pid))
+        {
+            self.selected_result = idx;
+        }
+    }
+
     /// Get currently selected query result
     pub fn get_selected_result(&self) -> Option<&QueryResult> {
         self.query_results.get(self.selected_result)
     }
+
+    /// Enter tree mode with a fresh tree snapshot
+    pub fn open_tree(&mut self, tree: ProcessTree) {
+        self.return_mode = self.input_mode;
+        self.tree = Some(tree);
+        self.tree_collapsed.clear();
+        self.tree_selected = 0;
+        self.refresh_tree_rows();
+        self.input_mode = InputMode::Tree;
+    }
+
+    /// Leave tree mode and return to the previous mode
+    pub fn close_tree(&mut self) {
+        self.input_mode = self.return_mode;
+        self.tree = None;
+        self.tree_rows.clear();
+    }
+
+    /// Recompute visible tree rows after expand/collapse
+    fn refresh_tree_rows(&mut self) {
+        self.tree_rows = match &self.tree {
+            Some(tree) => tree.flatten(&self.tree_collapsed),
+            None => Vec::new(),
+        };
+        if self.tree_selected >= self.tree_rows.len() {
+            self.tree_selected = self.tree_rows.len().saturating_sub(1);
+        }
+    }
+
+    /// Get PID of the selected tree row
+    pub fn selected_tree_pid(&self) -> Option<u32> {
+        self.tree_rows.get(self.tree_selected).map(|row| row.pid)
+    }
+
+    /// Select the tree row for a PID (if visible)
+    fn select_tree_pid(&mut self, pid: u32) {
+        if let Some(idx) = self.tree_rows.iter().position(|row| row.pid == pid) {
+            self.tree_selected = idx;
+        }
+    }
+
+    /// Navigate to previous tree row
+    pub fn tree_select_previous(&mut self) {
+        self.tree_selected = self.tree_selected.saturating_sub(1);
+    }
+
+    /// Navigate to next tree row
+    pub fn tree_select_next(&mut self) {
+        if self.tree_selected < self.tree_rows.len().saturating_sub(1) {
+            self.tree_selected += 1;
+        }
+    }
+
+    /// Navigate to first tree row
+    pub fn tree_select_first(&mut self) {
+        self.tree_selected = 0;
+    }
+
+    /// Navigate to last tree row
+    pub fn tree_select_last(&mut self) {
+        self.tree_selected = self.tree_rows.len().saturating_sub(1);
+    }
+
+    /// Collapse the selected subtree, or jump to the parent if already collapsed
+    pub fn tree_collapse(&mut self) {
+        let Some(row) = self.tree_rows.get(self.tree_selected) else {
+            return;
+        };
+        if row.has_children && row.expanded {
+            self.tree_collapsed.insert(row.pid);
+            self.refresh_tree_rows();
+        } else {
+            self.tree_jump_parent();
+        }
+    }
+
+    /// Expand the selected subtree, or jump to the first child if already expanded
+    pub fn tree_expand(&mut self) {
+        let Some(row) = self.tree_rows.get(self.tree_selected) else {
+            return;
+        };
+        if !row.has_children {
+            return;
+        }
+        if row.expanded {
+            self.tree_jump_child();
+        } else {
+            self.tree_collapsed.remove(&row.pid);
+            self.refresh_tree_rows();
+        }
+    }
+
+    /// Jump to the parent of the selected process
+    pub fn tree_jump_parent(&mut self) {
+        let parent = self
+            .selected_tree_pid()
+            .and_then(|pid| self.tree.as_ref().and_then(|t| t.parent(pid)));
+        if let Some(ppid) = parent {
+            self.select_tree_pid(ppid);
+        }
+    }
+
+    /// Jump to the first child of the selected process (expanding it if needed)
+    pub fn tree_jump_child(&mut self) {
+        let Some(pid) = self.selected_tree_pid() else {
+            return;
+        };
+        let first_child = self
+            .tree
+            .as_ref()
+            .and_then(|t| t.children(pid).first().copied());
+        if let Some(child) = first_child {
+            if self.tree_collapsed.remove(&pid) {
+                self.refresh_tree_rows();
+            }
+            self.select_tree_pid(child);
+        }
+    }
+
+    /// Enter audit mode with a fresh list of public listeners
+    pub fn open_audit(&mut self, entries: Vec<AuditEntry>) {
+        if self.input_mode != InputMode::Audit {
+            self.return_mode = self.input_mode;
+        }
+        self.audit_entries = entries;
+        self.audit_selected = self
+            .audit_selected
+            .min(self.audit_entries.len().saturating_sub(1));
+        self.input_mode = InputMode::Audit;
+    }
+
+    /// Leave audit mode and return to the previous mode
+    pub fn close_audit(&mut self) {
+        self.input_mode = self.return_mode;
+        self.audit_entries.clear();
+        self.audit_selected = 0;
+    }
+
+    /// Navigate to previous audit entry
+    pub fn audit_select_previous(&mut self) {
+        self.audit_selected = self.audit_selected.saturating_sub(1);
+    }
+
+    /// Navigate to next audit entry
+    pub fn audit_select_next(&mut self) {
+        if self.audit_selected < self.audit_entries.len().saturating_sub(1) {
+            self.audit_selected += 1;
+        }
+    }
+
+    /// Enter ports mode with a fresh port overview
+    pub fn open_ports(&mut self, groups: Vec<PortGroup>) {
+        if self.input_mode != InputMode::Ports {
+            self.return_mode = self.input_mode;
+        }
+        self.port_groups = groups;
+        self.ports_selected = self
+            .ports_selected
+            .min(self.port_groups.len().saturating_sub(1));
+        self.input_mode = InputMode::Ports;
+    }
+
+    /// Leave ports mode and return to the previous mode
+    pub fn close_ports(&mut self) {
+        self.input_mode = self.return_mode;
+        self.port_groups.clear();
+        self.ports_selected = 0;
+    }
+
+    /// Navigate to previous port group
+    pub fn ports_select_previous(&mut self) {
+        self.ports_selected = self.ports_selected.saturating_sub(1);
+    }
+
+    /// Navigate to next port group
+    pub fn ports_select_next(&mut self) {
+        if self.ports_selected < self.port_groups.len().saturating_sub(1) {
+            self.ports_selected += 1;
+        }
+    }
+
+    /// Enter health mode with a fresh health overview
+    pub fn open_health(&mut self, report: HealthReport) {
+        if self.input_mode != InputMode::Health {
+            self.return_mode = self.input_mode;
+        }
+        self.health_selected = self
+            .health_selected
+            .min(report.entries.len().saturating_sub(1));
+        self.health = Some(report);
+        self.input_mode = InputMode::Health;
+    }
+
+    /// Leave health mode and return to the previous mode
+    pub fn close_health(&mut self) {
+        self.input_mode = self.return_mode;
+        self.health = None;
+        self.health_selected = 0;
+    }
+
+    /// Navigate to previous health entry
+    pub fn health_select_previous(&mut self) {
+        self.health_selected = self.health_selected.saturating_sub(1);
+    }
+
+    /// Navigate to next health entry
+    pub fn health_select_next(&mut self) {
+        let count = self.health.as_ref().map_or(0, |h| h.entries.len());
+        if self.health_selected < count.saturating_sub(1) {
+            self.health_selected += 1;
+        }
+    }
+
+    /// Open the signal picker for the selected result
+    pub fn open_signal_picker(&mut self) {
+        if self.selected_process_alive() {
+            self.signal_selected = 0;
+            self.input_mode = InputMode::Signal;
+        }
+    }
+
+    /// Navigate to previous signal
+    pub fn signal_select_previous(&mut self) {
+        self.signal_selected = self.signal_selected.saturating_sub(1);
+    }
+
+    /// Navigate to next signal
+    pub fn signal_select_next(&mut self) {
+        if self.signal_selected < Signal::ALL.len() - 1 {
+            self.signal_selected += 1;
+        }
+    }
+
+    /// Get the signal highlighted in the picker
+    pub fn selected_signal(&self) -> Signal {
+        Signal::ALL[self.signal_selected.min(Signal::ALL.len() - 1)]
+    }
+
+    /// Open the renice prompt for the selected result
+    pub fn open_renice(&mut self) {
+        if self.selected_process_alive() {
+            self.renice_input.clear();
+            self.input_mode = InputMode::Renice;
+        }
+    }
+
+    /// Check that the selected result can be targeted by a signal or renice
+    ///
+    /// Exited results keep their PID, which may already belong to another process.
+    fn selected_process_alive(&mut self) -> bool {
+        match self.get_selected_result() {
+            Some(result) if result.exited => {
+                self.set_notification(format!(
+                    "PID {} has exited; refusing to act on it",
+                    result.process.pid
+                ));
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Ask for confirmation of an action on the selected result
+    pub fn request_action(&mut self, action: ProcessAction) {
+        let targets_pid = matches!(action, ProcessAction::Signal(_) | ProcessAction::Renice(_));
+        if targets_pid && !self.selected_process_alive() {
+            self.input_mode = InputMode::ViewResults;
+            return;
+        }
+        let Some(result) = self.get_selected_result() else {
+            return;
+        };
+
+        let process = &result.process;
+        let command = if process.cmdline.is_empty() {
+            format!("[{}]", process.name)
+        } else {
+            process.cmdline.join(" ")
+        };
+
+        self.pending_action = Some(PendingAction {
+            pid: process.pid,
+            start_ticks: process.start_ticks,
+            command,
+            user: process.user.clone(),
+            action,
+        });
+        self.input_mode = InputMode::Confirm;
+    }
+
+    /// Enter journal mode with a loaded journal view
+    pub fn open_journal(&mut self, journal: JournalView) {
+        self.journal = Some(journal);
+        self.input_mode = InputMode::Journal;
+    }
+
+    /// Leave journal mode and return to the results view
+    pub fn close_journal(&mut self) {
+        self.journal = None;
+        self.input_mode = InputMode::ViewResults;
+    }
+
+    /// Check whether the journal pane should poll for new lines
+    pub fn journal_follow_due(&self) -> bool {
+        self.input_mode == InputMode::Journal && self.journal.as_ref().is_some_and(|j| j.poll_due())
+    }
+
+    /// Drop any pending action and return to the results view
+    pub fn cancel_action(&mut self) {
+        self.pending_action = None;
+        self.renice_input.clear();
+        self.input_mode = InputMode::ViewResults;
+    }
 }
@@ -0,0 +1,434 @@
+use super::collector::read_environment;
+use super::container::{ContainerInfo, PodInfo, detect_container};
+use super::users::user_name;
+use procfs::process::Process;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Supervisor type for a process
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Supervisor {
+    /// System service or scope
+    Systemd {
+        unit: String,
+    },
+    /// Unit of a per-user manager (user@UID.service)
+    SystemdUser {
+        unit: String,
+        uid: u32,
+    },
+    /// Login session scope (session-N.scope)
+    Session(LoginSession),
+    Container(ContainerInfo),
+    Kubernetes {
+        container: ContainerInfo,
+        pod: PodInfo,
+    },
+    /// cron/crond/anacron job
+    Cron {
+        daemon: String,
+        pid: u32,
+    },
+    Tmux {
+        server_pid: u32,
+        socket: Option<String>,
+    },
+    Screen {
+        server_pid: u32,
+        session: Option<String>,
+    },
+    Ssh(SshSession),
+    Supervisord {
+        pid: u32,
+        program: Option<String>,
+    },
+    Runit {
+        pid: u32,
+        service: Option<String>,
+    },
+    S6 {
+        pid: u32,
+        service: Option<String>,
+    },
+    /// Started by (or reparented to) PID 1 outside any unit
+    Init,
+    Shell,
+    Unknown,
+}
+
+/// systemd-logind session (from /run/systemd/sessions/<id>)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoginSession {
+    pub id: String,
+    pub user: Option<String>,
+    pub service: Option<String>, // PAM service: sshd, login, gdm-password, ...
+    pub tty: Option<String>,
+    pub remote_host: Option<String>,
+}
+
+/// Interactive SSH session a process was started from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SshSession {
+    pub sshd_pid: u32,
+    pub remote_ip: Option<String>,
+    pub tty: Option<String>,
+}
+
+/// Maximum number of ancestors inspected for a supervising process
+const MAX_ANCESTOR_DEPTH: usize = 64;
+
+/// Shells that count as an interactive parent
+const SHELLS: [&str; 7] = ["bash", "zsh", "sh", "dash", "fish", "ksh", "tcsh"];
+
+/// Short supervisor label for list views
+pub fn supervisor_label(supervisor: &Supervisor) -> String {
+    match supervisor {
+        Supervisor::Systemd { unit } => format!("systemd: {}", unit),
+        Supervisor::SystemdUser { unit, uid } => {
+            format!("systemd --user ({}): {}", user_name(*uid), unit)
+        }
+        Supervisor::Session(session) => format!("session {}", session.id),
+        Supervisor::Container(container) => {
+            format!(
+                "{}: {}",
+                container.runtime.as_str(),
+                container.display_name()
+            )
+        }
+        Supervisor::Kubernetes { container, pod } => match (&pod.namespace, &pod.name) {
+            (Some(ns), Some(name)) => format!("k8s: {}/{}", ns, name),
+            _ => format!("k8s: {}", container.display_name()),
+        },
+        Supervisor::Cron { daemon, .. } => daemon.clone(),
+        Supervisor::Tmux { .. } => "tmux".to_string(),
+        Supervisor::Screen { .. } => "screen".to_string(),
+        Supervisor::Ssh(ssh) => match ssh.remote_ip {
+            Some(ref ip) => format!("ssh from {}", ip),
+            None => "ssh".to_string(),
+        },
+        Supervisor::Supervisord { program, .. } => {
+            format!("supervisord: {}", program.as_deref().unwrap_or("?"))
+        }
+        Supervisor::Runit { service, .. } => {
+            format!("runit: {}", service.as_deref().unwrap_or("?"))
+        }
+        Supervisor::S6 { service, .. } => format!("s6: {}", service.as_deref().unwrap_or("?")),
+        Supervisor::Init => "init".to_string(),
+        Supervisor::Shell => "shell".to_string(),
+        Supervisor::Unknown => "unknown".to_string(),
+    }
+}
+
+/// Variant-specific supervisor details for the details panel
+pub fn supervisor_details(supervisor: &Supervisor) -> Vec<(&'static str, String)> {
+    let pid_field = |label, pid: &u32| (label, pid.to_string());
+    let opt_field = |label, value: &Option<String>| value.clone().map(|v| (label, v));
+
+    match supervisor {
+        Supervisor::Systemd { unit } => vec![
+            ("Type:      ", "systemd system service".to_string()),
+            ("Unit:      ", unit.clone()),
+        ],
+        Supervisor::SystemdUser { unit, uid } => vec![
+            ("Type:      ", "systemd user service".to_string()),
+            ("Unit:      ", unit.clone()),
+            (
+                "Manager:   ",
+                format!("user@{}.service ({})", uid, user_name(*uid)),
+            ),
+        ],
+        Supervisor::Session(session) => {
+            let mut fields = vec![
+                ("Type:      ", "login session".to_string()),
+                ("Session:   ", session.id.clone()),
+            ];
+            fields.extend(opt_field("User:      ", &session.user));
+            fields.extend(opt_field("Service:   ", &session.service));
+            fields.extend(opt_field("TTY:       ", &session.tty));
+            fields.extend(opt_field("Remote:    ", &session.remote_host));
+            fields
+        }
+        Supervisor::Cron { daemon, pid } => vec![
+            ("Type:      ", format!("{} job", daemon)),
+            pid_field("Daemon PID:", pid),
+        ],
+        Supervisor::Tmux { server_pid, socket } => {
+            let mut fields = vec![
+                ("Type:      ", "tmux pane".to_string()),
+                pid_field("Server PID:", server_pid),
+            ];
+            fields.extend(opt_field("Socket:    ", socket));
+            fields
+        }
+        Supervisor::Screen {
+            server_pid,
+            session,
+        } => {
+            let mut fields = vec![
+                ("Type:      ", "screen window".to_string()),
+                pid_field("Server PID:", server_pid),
+            ];
+            fields.extend(opt_field("Session:   ", session));
+            fields
+        }
+        Supervisor::Ssh(ssh) => {
+            let mut fields = vec![
+                ("Type:      ", "ssh session".to_string()),
+                pid_field("sshd PID:  ", &ssh.sshd_pid),
+            ];
+            fields.extend(opt_field("Remote IP: ", &ssh.remote_ip));
+            fields.extend(opt_field("TTY:       ", &ssh.tty));
+            fields
+        }
+        Supervisor::Supervisord { pid, program } => {
+            let mut fields = vec![
+                ("Type:      ", "supervisord program".to_string()),
+                pid_field("Daemon PID:", pid),
+            ];
+            fields.extend(opt_field("Program:   ", program));
+            fields
+        }
+        Supervisor::Runit { pid, service } | Supervisor::S6 { pid, service } => {
+            let kind = if matches!(supervisor, Supervisor::Runit { .. }) {
+                "runit service"
+            } else {
+                "s6 service"
+            };
+            let mut fields = vec![
+                ("Type:      ", kind.to_string()),
+                pid_field("Supervise: ", pid),
+            ];
+            fields.extend(opt_field("Service:   ", service));
+            fields
+        }
+        Supervisor::Init => vec![(
+            "Type:      ",
+            "started by or reparented to PID 1 (no unit)".to_string(),
+        )],
+        // Containers have their own section; shells need no details
+        Supervisor::Container(_)
+        | Supervisor::Kubernetes { .. }
+        | Supervisor::Shell
+        | Supervisor::Unknown => Vec::new(),
+    }
+}
+
+/// Detect supervisor for a process
+///
+/// Order matters: containers are recognised by cgroup first, then the nearest
+/// supervising ancestor (tmux, cron, sshd, ...) wins over the systemd unit it
+/// runs in, since e.g. every cron job also sits in cron.service.
+pub fn detect_supervisor(pid: u32) -> Supervisor {
+    let cgroup_content =
+        std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).unwrap_or_default();
+
+    let uid = Process::new(pid as i32).and_then(|p| p.uid()).unwrap_or(0);
+    if let Some((container, pod)) = detect_container(&cgroup_content, uid) {
+        return match pod {
+            Some(pod) => Supervisor::Kubernetes { container, pod },
+            None => Supervisor::Container(container),
+        };
+    }
+
+    if pid == 1 {
+        return Supervisor::Unknown;
+    }
+
+    if let Some(supervisor) = detect_ancestor_supervisor(pid) {
+        return supervisor;
+    }
+
+    if let Some(supervisor) = detect_systemd_unit(&cgroup_content) {
+        return supervisor;
+    }
+
+    match read_parent(pid) {
+        Some((1, _)) => Supervisor::Init,
+        Some((_, comm)) if SHELLS.contains(&comm.as_str()) => Supervisor::Shell,
+        _ => Supervisor::Unknown,
+    }
+}
+
+/// Read PPID and comm of a process
+fn read_parent(pid: u32) -> Option<(u32, String)> {
+    let stat = Process::new(pid as i32).ok()?.stat().ok()?;
+    let ppid = stat.ppid as u32;
+    let parent = Process::new(ppid as i32).ok()?.stat().ok()?;
+    Some((ppid, parent.comm))
+}
+
+fn read_cmdline(pid: u32) -> Vec<String> {
+    Process::new(pid as i32)
+        .and_then(|p| p.cmdline())
+        .unwrap_or_default()
+}
+
+/// Walk up the ancestors looking for a supervising process
+fn detect_ancestor_supervisor(pid: u32) -> Option<Supervisor> {
+    let mut current = pid;
+
+    for _ in 0..MAX_ANCESTOR_DEPTH {
+        let (ppid, comm) = read_parent(current)?;
+        // PID 1 and per-user managers are handled via cgroups
+        if ppid <= 1 || comm == "systemd" {
+            return None;
+        }
+
+        let supervisor = match comm.as_str() {
+            "cron" | "crond" | "anacron" => Some(Supervisor::Cron {
+                daemon: comm.clone(),
+                pid: ppid,
+            }),
+            "tmux: server" | "tmux" => Some(Supervisor::Tmux {
+                server_pid: ppid,
+                // TMUX=<socket>,<server pid>,<session index>
+                socket: env_var(pid, "TMUX").and_then(|v| v.split(',').next().map(str::to_string)),
+            }),
+            "screen" | "SCREEN" => Some(Supervisor::Screen {
+                server_pid: ppid,
+                // STY=<pid>.<tty>.<host>
+                session: env_var(pid, "STY"),
+            }),
+            // Only session children: the listener's own per-connection children are not
+            "sshd" | "sshd-session" if is_ssh_session_child(current) => {
+                Some(Supervisor::Ssh(read_ssh_session(pid, ppid)))
+            }
+            "supervisord" => Some(Supervisor::Supervisord {
+                pid: ppid,
+                program: env_var(pid, "SUPERVISOR_PROCESS_NAME"),
+            }),
+            "runsv" => Some(Supervisor::Runit {
+                pid: ppid,
+                service: read_cmdline(ppid).get(1).cloned(),
+            }),
+            "s6-supervise" => Some(Supervisor::S6 {
+                pid: ppid,
+                service: read_cmdline(ppid).get(1).cloned(),
+            }),
+            _ => None,
+        };
+
+        if supervisor.is_some() {
+            return supervisor;
+        }
+        current = ppid;
+    }
+
+    None
+}
+
+/// Check whether a child of sshd is a user session (login shell or remote command)
+///
+/// sshd's own processes (the per-connection monitor forked by the listener,
+/// the privilege-separated child) have neither a controlling tty nor
+/// SSH_CONNECTION, which sshd only sets in the session environment.
+fn is_ssh_session_child(pid: u32) -> bool {
+    let has_tty = Process::new(pid as i32)
+        .and_then(|p| p.stat())
+        .is_ok_and(|stat| stat.tty_nr != 0);
+    has_tty || env_var(pid, "SSH_CONNECTION").is_some()
+}
+
+/// Read a single environment variable (needs same user or root)
+fn env_var(pid: u32, key: &str) -> Option<String> {
+    read_environment(pid).ok()?.remove(key)
+}
+
+/// Describe the SSH session served by an sshd process
+fn read_ssh_session(pid: u32, sshd_pid: u32) -> SshSession {
+    // "sshd: alice@pts/0" → "pts/0"
+    let tty = read_cmdline(sshd_pid)
+        .first()
+        .and_then(|title| title.split_once('@'))
+        .map(|(_, tty)| tty.trim().to_string());
+
+    // SSH_CONNECTION="<client ip> <client port> <server ip> <server port>"
+    let remote_ip = env_var(pid, "SSH_CONNECTION")
+        .or_else(|| env_var(pid, "SSH_CLIENT"))
+        .and_then(|v| v.split_whitespace().next().map(str::to_string));
+
+    SshSession {
+        sshd_pid,
+        remote_ip,
+        tty,
+    }
+}
+
+/// Get the systemd cgroup path ("0::/..." on v2, "N:name=systemd:/..." on v1)
+fn systemd_cgroup_path(cgroup_content: &str) -> Option<&str> {
+    let mut fallback = None;
+
+    for line in cgroup_content.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        match controllers {
+            "name=systemd" => return Some(path),
+            // Hybrid setups also list an (empty) v2 hierarchy
+            "" if path != "/" => fallback = Some(path),
+            _ => {}
+        }
+    }
+
+    fallback
+}
+
+/// Detect the systemd unit a process runs in from its cgroup path
+///
+/// /system.slice/nginx.service                                → Systemd
+/// /user.slice/user-1000.slice/user@1000.service/app.slice/x.service → SystemdUser
+/// /user.slice/user-1000.slice/session-3.scope                → Session
+fn detect_systemd_unit(cgroup_content: &str) -> Option<Supervisor> {
+    let path = systemd_cgroup_path(cgroup_content)?;
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let unit = segments
+        .iter()
+        .rev()
+        .find(|s| s.ends_with(".service") || s.ends_with(".scope"))?;
+
+    // Anything below user@UID.service belongs to the per-user manager
+    if let Some(uid) = segments.iter().find_map(|s| {
+        s.strip_prefix("user@")
+            .and_then(|rest| rest.strip_suffix(".service"))
+            .and_then(|uid| uid.parse::<u32>().ok())
+    }) {
+        return Some(Supervisor::SystemdUser {
+            unit: unit.to_string(),
+            uid,
+        });
+    }
+
+    if let Some(id) = unit
+        .strip_prefix("session-")
+        .and_then(|s| s.strip_suffix(".scope"))
+    {
+        return Some(Supervisor::Session(read_login_session(id)));
+    }
+
+    Some(Supervisor::Systemd {
+        unit: unit.to_string(),
+    })
+}
+
+/// Read session details written by systemd-logind
+fn read_login_session(id: &str) -> LoginSession {
+    let fields: HashMap<String, String> =
+        std::fs::read_to_string(format!("/run/systemd/sessions/{}", id))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+    LoginSession {
+        id: id.to_string(),
+        user: fields.get("USER").cloned(),
+        service: fields.get("SERVICE").cloned(),
+        tty: fields.get("TTY").cloned(),
+        remote_host: fields.get("REMOTE_HOST").cloned(),
+    }
+}
@@ -1,9 +1,11 @@
 use color_eyre::Result;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-/// Rich systemd metadata from `systemctl show`
-#[derive(Debug, Clone)]
+/// Rich systemd metadata from `systemctl show` (or unit files as fallback)
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemdMetadata {
     pub unit_name: String,
     pub description: Option<String>,
@@ -14,49 +16,276 @@ pub struct SystemdMetadata {
     pub exec_start: Option<String>,
     pub restart_policy: Option<String>,
     pub wanted_by: Vec<String>,
+    pub triggered_by: Vec<String>, // .timer/.socket/.path units that start this one
+    pub requires: Vec<String>,
+    pub after: Vec<String>,
+    pub part_of: Vec<String>,
+    pub memory_current: Option<u64>, // bytes
+    pub cpu_usage_nsec: Option<u64>,
+    pub tasks_current: Option<u64>,
+    pub n_restarts: Option<u32>,
+    pub last_exit: Option<String>, // "exited, status=1" / "killed, signal=9"
+    pub fragment_path: Option<String>,
+    pub drop_in_paths: Vec<String>,
 }
 
-/// Fetch rich metadata from systemctl show
-pub fn fetch_systemd_metadata(unit_name: &str) -> Result<SystemdMetadata> {
-    let output = Command::new("systemctl")
-        .args(["show", unit_name])
+/// Properties requested from `systemctl show`
+const SHOW_PROPERTIES: &[&str] = &[
+    "Id",
+    "Description",
+    "LoadState",
+    "ActiveState",
+    "SubState",
+    "MainPID",
+    "ExecStart",
+    "Restart",
+    "WantedBy",
+    "TriggeredBy",
+    "Requires",
+    "After",
+    "PartOf",
+    "MemoryCurrent",
+    "CPUUsageNSec",
+    "TasksCurrent",
+    "NRestarts",
+    "ExecMainCode",
+    "ExecMainStatus",
+    "ExecMainExitTimestamp",
+    "FragmentPath",
+    "DropInPaths",
+];
+
+/// Unit search path, highest priority first (see systemd.unit(5))
+const UNIT_DIRS: &[&str] = &[
+    "/etc/systemd/system",
+    "/run/systemd/system",
+    "/usr/local/lib/systemd/system",
+    "/usr/lib/systemd/system",
+    "/lib/systemd/system",
+];
+
+/// Units whose activation implies "triggered by" (same name, different suffix)
+const TRIGGER_SUFFIXES: &[&str] = &["timer", "socket", "path"];
+
+/// systemctl binary (tests inject a stand-in through `SystemdClient::with_paths`)
+pub const SYSTEMCTL: &str = "systemctl";
+
+/// Unit directories; `SYSTEMD_UNIT_PATH` overrides them like it does for systemd
+fn unit_dirs() -> Vec<PathBuf> {
+    match std::env::var("SYSTEMD_UNIT_PATH") {
+        Ok(path) => path
+            .split(':')
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .collect(),
+        Err(_) => UNIT_DIRS.iter().map(PathBuf::from).collect(),
+    }
+}
+
+/// Systemd data layer: batches `systemctl show` calls and caches per query
+///
+/// All units of a result set are fetched with one `systemctl show` call. When
+/// systemctl is missing or has no running systemd to talk to (containers,
+/// chroots), metadata is read from unit files instead.
+#[derive(Debug)]
+pub struct SystemdClient {
+    cache: HashMap<String, Option<SystemdMetadata>>,
+    systemctl: String,                 // SYSTEMCTL outside tests
+    unit_dirs: Vec<PathBuf>,           // unit file fallback, see unit_dirs()
+    systemctl_available: Option<bool>, // false once the binary is known to be missing
+}
+
+impl SystemdClient {
+    pub fn new() -> Self {
+        Self::with_paths(SYSTEMCTL.to_string(), unit_dirs())
+    }
+
+    /// Client with an explicit systemctl binary and unit directories
+    fn with_paths(systemctl: String, unit_dirs: Vec<PathBuf>) -> Self {
+        Self {
+            cache: HashMap::new(),
+            systemctl,
+            unit_dirs,
+            systemctl_available: None,
+        }
+    }
+
+    /// Drop cached metadata (called once per query)
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Fetch metadata for several units, one subprocess for all uncached ones
+    pub fn prefetch(&mut self, units: &[String]) {
+        let mut missing: Vec<String> = Vec::new();
+        for unit in units {
+            if !self.cache.contains_key(unit) && !missing.contains(unit) {
+                missing.push(unit.clone());
+            }
+        }
+        if missing.is_empty() {
+            return;
+        }
+
+        if self.systemctl_available != Some(false) {
+            match show_units(&self.systemctl, &missing) {
+                Ok(fetched) => {
+                    self.systemctl_available = Some(true);
+                    for (unit, metadata) in missing.iter().zip(fetched) {
+                        self.cache.insert(unit.clone(), Some(metadata));
+                    }
+                    return;
+                }
+                // Only a missing binary is permanent; other failures are retried next query
+                Err(e)
+                    if e.downcast_ref::<std::io::Error>()
+                        .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound) =>
+                {
+                    self.systemctl_available = Some(false);
+                }
+                Err(_) => {}
+            }
+        }
+
+        for unit in missing {
+            let metadata = read_unit_files(&unit, &self.unit_dirs);
+            self.cache.insert(unit, metadata);
+        }
+    }
+
+    /// Get metadata for a unit (fetched on demand if not prefetched)
+    pub fn get(&mut self, unit: &str) -> Option<SystemdMetadata> {
+        if !self.cache.contains_key(unit) {
+            self.prefetch(&[unit.to_string()]);
+        }
+        self.cache.get(unit).cloned().flatten()
+    }
+}
+
+impl Default for SystemdClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run one `systemctl show` for all units
+///
+/// The output has one KEY=VALUE block per unit, separated by blank lines, in
+/// argument order.
+fn show_units(systemctl: &str, units: &[String]) -> Result<Vec<SystemdMetadata>> {
+    let output = Command::new(systemctl)
+        .arg("show")
+        .arg(format!("--property={}", SHOW_PROPERTIES.join(",")))
+        .arg("--")
+        .args(units)
         .output()?;
 
     if !output.status.success() {
         return Err(color_eyre::eyre::eyre!(
-            "systemctl show failed for {}",
-            unit_name
+            "systemctl show failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
         ));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let properties = parse_systemctl_output(&stdout);
+    let blocks: Vec<HashMap<String, String>> = stdout
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(parse_systemctl_output)
+        .collect();
 
-    Ok(SystemdMetadata {
-        unit_name: unit_name.to_string(),
-        description: properties.get("Description").cloned(),
-        load_state: properties
-            .get("LoadState")
-            .cloned()
-            .unwrap_or_else(|| "unknown".to_string()),
-        active_state: properties
-            .get("ActiveState")
-            .cloned()
-            .unwrap_or_else(|| "unknown".to_string()),
-        sub_state: properties
-            .get("SubState")
+    if blocks.len() != units.len() {
+        return Err(color_eyre::eyre::eyre!(
+            "systemctl show returned {} blocks for {} units",
+            blocks.len(),
+            units.len()
+        ));
+    }
+
+    Ok(units
+        .iter()
+        .zip(blocks)
+        .map(|(unit, properties)| metadata_from_properties(unit, &properties))
+        .collect())
+}
+
+fn split_list(value: Option<&String>) -> Vec<String> {
+    value
+        .map(|s| s.split_whitespace().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Parse a resource counter; systemd reports "[not set]" or u64::MAX when unknown
+fn parse_counter(value: Option<&String>) -> Option<u64> {
+    value
+        .and_then(|s| s.parse::<u64>().ok())
+        .filter(|&v| v != u64::MAX)
+}
+
+/// Describe how the main process last exited
+///
+/// ExecMainCode is a CLD_* code from waitid(2): 1 = exited, 2 = killed, 3 = dumped.
+fn describe_last_exit(properties: &HashMap<String, String>) -> Option<String> {
+    let code = properties.get("ExecMainCode")?.parse::<i32>().ok()?;
+    let status = properties
+        .get("ExecMainStatus")
+        .map(String::as_str)
+        .unwrap_or("?");
+
+    let mut description = match code {
+        1 => format!("exited, status={}", status),
+        2 => format!("killed, signal={}", status),
+        3 => format!("dumped core, signal={}", status),
+        _ => return None,
+    };
+
+    if let Some(timestamp) = properties
+        .get("ExecMainExitTimestamp")
+        .filter(|t| !t.is_empty())
+    {
+        description.push_str(&format!(" ({})", timestamp));
+    }
+
+    Some(description)
+}
+
+fn metadata_from_properties(unit: &str, properties: &HashMap<String, String>) -> SystemdMetadata {
+    let state = |key: &str| {
+        properties
+            .get(key)
             .cloned()
-            .unwrap_or_else(|| "unknown".to_string()),
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    SystemdMetadata {
+        unit_name: unit.to_string(),
+        description: properties.get("Description").cloned(),
+        load_state: state("LoadState"),
+        active_state: state("ActiveState"),
+        sub_state: state("SubState"),
         main_pid: properties
             .get("MainPID")
             .and_then(|s| s.parse::<u32>().ok()),
         exec_start: properties.get("ExecStart").cloned(),
         restart_policy: properties.get("Restart").cloned(),
-        wanted_by: properties
-            .get("WantedBy")
-            .map(|s| s.split_whitespace().map(String::from).collect())
-            .unwrap_or_default(),
-    })
+        wanted_by: split_list(properties.get("WantedBy")),
+        triggered_by: split_list(properties.get("TriggeredBy")),
+        requires: split_list(properties.get("Requires")),
+        after: split_list(properties.get("After")),
+        part_of: split_list(properties.get("PartOf")),
+        memory_current: parse_counter(properties.get("MemoryCurrent")),
+        cpu_usage_nsec: parse_counter(properties.get("CPUUsageNSec")),
+        tasks_current: parse_counter(properties.get("TasksCurrent")),
+        n_restarts: properties
+            .get("NRestarts")
+            .and_then(|s| s.parse::<u32>().ok()),
+        last_exit: describe_last_exit(properties),
+        fragment_path: properties
+            .get("FragmentPath")
+            .filter(|p| !p.is_empty())
+            .cloned(),
+        drop_in_paths: split_list(properties.get("DropInPaths")),
+    }
 }
 
 /// Parse systemctl show output (KEY=VALUE format)
@@ -71,3 +300,230 @@ fn parse_systemctl_output(output: &str) -> HashMap<String, String> {
 
     props
 }
+
+/// Find the unit file, falling back to the template for instances (foo@bar → foo@)
+fn find_fragment(unit: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    let mut names = vec![unit.to_string()];
+    if let Some((prefix, rest)) = unit.split_once('@')
+        && let Some((_, suffix)) = rest.rsplit_once('.')
+    {
+        names.push(format!("{}@.{}", prefix, suffix));
+    }
+
+    names.iter().find_map(|name| {
+        dirs.iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.exists())
+    })
+}
+
+/// Drop-in snippets (<unit>.d/*.conf) from all directories, lowest priority first
+fn find_dropins(unit: &str, dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut dropins: Vec<PathBuf> = Vec::new();
+
+    for dir in dirs.iter().rev() {
+        let Ok(entries) = std::fs::read_dir(dir.join(format!("{}.d", unit))) else {
+            continue;
+        };
+        let mut confs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+            .collect();
+        confs.sort();
+        dropins.extend(confs);
+    }
+
+    dropins
+}
+
+/// Apply one unit file to a section.key → values map
+///
+/// List settings accumulate; an empty assignment resets them (systemd semantics).
+fn apply_unit_file(path: &Path, settings: &mut HashMap<String, Vec<String>>) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+
+    let mut section = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let values = settings
+            .entry(format!("{}.{}", section, key.trim()))
+            .or_default();
+        let value = value.trim();
+        if value.is_empty() {
+            values.clear();
+        } else {
+            values.push(value.to_string());
+        }
+    }
+}
+
+/// Build metadata from unit files when systemctl is unavailable
+///
+/// Runtime state (active state, main PID) is unknown in this mode.
+fn read_unit_files(unit: &str, dirs: &[PathBuf]) -> Option<SystemdMetadata> {
+    let fragment = find_fragment(unit, dirs)?;
+    let dropins = find_dropins(unit, dirs);
+
+    let mut settings: HashMap<String, Vec<String>> = HashMap::new();
+    apply_unit_file(&fragment, &mut settings);
+    for dropin in &dropins {
+        apply_unit_file(dropin, &mut settings);
+    }
+
+    let last = |key: &str| settings.get(key).and_then(|v| v.last().cloned());
+    let list = |key: &str| -> Vec<String> {
+        settings
+            .get(key)
+            .map(|values| {
+                values
+                    .iter()
+                    .flat_map(|v| v.split_whitespace().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let masked = std::fs::canonicalize(&fragment).is_ok_and(|p| p == Path::new("/dev/null"));
+    let stem = unit.rsplit_once('.').map_or(unit, |(stem, _)| stem);
+    let triggered_by = TRIGGER_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}.{}", stem, suffix))
+        .filter(|trigger| find_fragment(trigger, dirs).is_some())
+        .collect();
+
+    Some(SystemdMetadata {
+        unit_name: unit.to_string(),
+        description: last("Unit.Description"),
+        load_state: if masked { "masked" } else { "loaded" }.to_string(),
+        active_state: "unknown".to_string(),
+        sub_state: "unknown".to_string(),
+        main_pid: None,
+        exec_start: settings
+            .get("Service.ExecStart")
+            .filter(|v| !v.is_empty())
+            .map(|v| v.join("; ")),
+        restart_policy: last("Service.Restart").or_else(|| Some("no".to_string())),
+        wanted_by: list("Install.WantedBy"),
+        triggered_by,
+        requires: list("Unit.Requires"),
+        after: list("Unit.After"),
+        part_of: list("Unit.PartOf"),
+        memory_current: None,
+        cpu_usage_nsec: None,
+        tasks_current: None,
+        n_restarts: None,
+        last_exit: None,
+        fragment_path: Some(fragment.to_string_lossy().to_string()),
+        drop_in_paths: dropins
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::proctrace::test_support::ScratchDir;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn show_units_splits_blocks_in_argument_order() {
+        let dir = ScratchDir::new("systemctl-show");
+        // Trailing blank lines and empty blocks must not count as units
+        let systemctl = dir.script(
+            "systemctl",
+            "printf 'Id=a.service\\nActiveState=active\\nMainPID=42\\nWantedBy=multi-user.target default.target\\n\\n'\n\
+             printf 'Id=b.service\\nActiveState=failed\\nMemoryCurrent=18446744073709551615\\n\\n\\n\\n'",
+        );
+
+        let metadata = show_units(&systemctl, &names(&["a.service", "b.service"])).unwrap();
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata[0].unit_name, "a.service");
+        assert_eq!(metadata[0].active_state, "active");
+        assert_eq!(metadata[0].main_pid, Some(42));
+        assert_eq!(
+            metadata[0].wanted_by,
+            names(&["multi-user.target", "default.target"])
+        );
+        assert_eq!(metadata[1].unit_name, "b.service");
+        assert_eq!(metadata[1].active_state, "failed");
+        assert_eq!(metadata[1].memory_current, None);
+    }
+
+    #[test]
+    fn show_units_rejects_block_count_mismatch() {
+        let dir = ScratchDir::new("systemctl-mismatch");
+        let systemctl = dir.script("systemctl", "printf 'Id=a.service\\n\\n\\n'");
+
+        assert!(show_units(&systemctl, &names(&["a.service", "b.service"])).is_err());
+    }
+
+    #[test]
+    fn prefetch_falls_back_to_unit_files_and_retries() {
+        let dir = ScratchDir::new("systemctl-fallback");
+        let systemctl = dir.script(
+            "systemctl",
+            "echo 'System has not been booted with systemd' >&2\nexit 1",
+        );
+        dir.write(
+            "units/web.service",
+            "[Unit]\nDescription=Web server\nRequires=db.service\nAfter=network.target db.service\n\n\
+             [Service]\nExecStart=/usr/bin/web --old\nRestart=on-failure\n\n\
+             [Install]\nWantedBy=multi-user.target\n",
+        );
+        dir.write(
+            "units/web.service.d/override.conf",
+            "[Service]\nExecStart=\nExecStart=/usr/bin/web --new\n",
+        );
+        dir.write("units/web.timer", "[Timer]\nOnCalendar=daily\n");
+
+        let mut client = SystemdClient::with_paths(systemctl, vec![dir.path().join("units")]);
+        let meta = client.get("web.service").unwrap();
+
+        assert_eq!(meta.description.as_deref(), Some("Web server"));
+        assert_eq!(meta.exec_start.as_deref(), Some("/usr/bin/web --new"));
+        assert_eq!(meta.restart_policy.as_deref(), Some("on-failure"));
+        assert_eq!(meta.requires, names(&["db.service"]));
+        assert_eq!(meta.after, names(&["network.target", "db.service"]));
+        assert_eq!(meta.wanted_by, names(&["multi-user.target"]));
+        assert_eq!(meta.triggered_by, names(&["web.timer"]));
+        assert_eq!(meta.active_state, "unknown");
+        assert_eq!(meta.drop_in_paths.len(), 1);
+        assert!(client.get("missing.service").is_none());
+
+        // A failing systemctl is tried again on the next query
+        assert_eq!(client.systemctl_available, None);
+    }
+
+    #[test]
+    fn prefetch_stops_using_a_missing_systemctl() {
+        let dir = ScratchDir::new("systemctl-missing");
+        let systemctl = dir.path().join("no-such-systemctl");
+        let mut client = SystemdClient::with_paths(
+            systemctl.to_string_lossy().to_string(),
+            vec![dir.path().to_path_buf()],
+        );
+
+        assert!(client.get("web.service").is_none());
+        assert_eq!(client.systemctl_available, Some(false));
+    }
+}
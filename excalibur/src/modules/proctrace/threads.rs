@@ -0,0 +1,63 @@
+use procfs::process::Process;
+use serde::Serialize;
+
+/// One thread from /proc/<pid>/task
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    pub state: char,           // R, S, D, Z, T, t, I, ...
+    pub cpu_time: f64,         // user + system, seconds
+    pub wchan: Option<String>, // kernel function the thread sleeps in
+}
+
+/// Describe a scheduler state letter (see proc(5))
+pub fn state_description(state: char) -> &'static str {
+    match state {
+        'R' => "running",
+        'S' => "sleeping",
+        'D' => "disk sleep",
+        'Z' => "zombie",
+        'T' => "stopped",
+        't' => "tracing stop",
+        'X' | 'x' => "dead",
+        'I' => "idle",
+        'P' => "parked",
+        'W' => "waking",
+        'K' => "wakekill",
+        _ => "unknown",
+    }
+}
+
+/// Read wchan of a thread; "0" means it is not blocked in the kernel
+fn read_wchan(pid: u32, tid: u32) -> Option<String> {
+    let wchan = std::fs::read_to_string(format!("/proc/{}/task/{}/wchan", pid, tid)).ok()?;
+    let wchan = wchan.trim();
+    (!wchan.is_empty() && wchan != "0").then(|| wchan.to_string())
+}
+
+/// Get all threads of a process, sorted by TID
+pub fn read_threads(pid: u32) -> Vec<ThreadInfo> {
+    let Ok(tasks) = Process::new(pid as i32).and_then(|p| p.tasks()) else {
+        return Vec::new();
+    };
+    let ticks_per_second = procfs::ticks_per_second() as f64;
+
+    let mut threads: Vec<ThreadInfo> = tasks
+        .flatten()
+        .filter_map(|task| {
+            let stat = task.stat().ok()?;
+            let tid = task.tid as u32;
+            Some(ThreadInfo {
+                tid,
+                name: stat.comm,
+                state: stat.state,
+                cpu_time: (stat.utime + stat.stime) as f64 / ticks_per_second,
+                wchan: read_wchan(pid, tid),
+            })
+        })
+        .collect();
+
+    threads.sort_by_key(|t| t.tid);
+    threads
+}
@@ -0,0 +1,382 @@
+use super::collector::ProcessInfo;
+use std::collections::{HashMap, HashSet};
+
+/// Aggregated metrics for a process and all of its descendants
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubtreeRollup {
+    pub cpu_percent: f32,
+    pub memory_rss: u64,
+    pub process_count: usize,
+}
+
+/// A visible row of the flattened tree
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    pub pid: u32,
+    pub prefix: String, // pstree-style guides, e.g. "│  ├─ "
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+/// Process tree built from a process snapshot using PPID links
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTree {
+    processes: HashMap<u32, ProcessInfo>,
+    children: HashMap<u32, Vec<u32>>,
+    roots: Vec<u32>,
+    rollups: HashMap<u32, SubtreeRollup>,
+}
+
+impl ProcessTree {
+    /// Build a tree of all given processes
+    pub fn build(processes: Vec<ProcessInfo>) -> Self {
+        let processes: HashMap<u32, ProcessInfo> =
+            processes.into_iter().map(|p| (p.pid, p)).collect();
+
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for process in processes.values() {
+            // A process whose parent is not in the snapshot becomes a root
+            // (PID 1, kthreadd, or processes whose parent just exited)
+            if process.pid != process.ppid && processes.contains_key(&process.ppid) {
+                children.entry(process.ppid).or_default().push(process.pid);
+            } else {
+                roots.push(process.pid);
+            }
+        }
+
+        roots.sort_unstable();
+        for kids in children.values_mut() {
+            kids.sort_unstable();
+        }
+
+        let mut tree = Self {
+            processes,
+            children,
+            roots,
+            rollups: HashMap::new(),
+        };
+        tree.compute_rollups();
+        tree
+    }
+
+    /// Build a tree containing only the given root processes and their descendants
+    ///
+    /// Roots that are themselves descendants of another root are folded into
+    /// that root's subtree instead of being listed twice.
+    pub fn build_subtrees(processes: Vec<ProcessInfo>, root_pids: &[u32]) -> Self {
+        let full = Self::build(processes);
+        let wanted: HashSet<u32> = root_pids.iter().copied().collect();
+
+        let mut keep = HashSet::new();
+        let mut roots = Vec::new();
+        for &pid in root_pids {
+            if !full.processes.contains_key(&pid) || keep.contains(&pid) {
+                continue;
+            }
+
+            // Skip roots nested under another requested root
+            let mut ancestor = full.parent(pid);
+            let mut nested = false;
+            while let Some(ppid) = ancestor {
+                if wanted.contains(&ppid) {
+                    nested = true;
+                    break;
+                }
+                ancestor = full.parent(ppid);
+            }
+            if nested {
+                continue;
+            }
+
+            roots.push(pid);
+            full.collect_descendants(pid, &mut keep);
+        }
+
+        let mut processes = full.processes;
+        processes.retain(|pid, _| keep.contains(pid));
+        let mut children = full.children;
+        children.retain(|pid, _| keep.contains(pid));
+
+        roots.sort_unstable();
+        roots.dedup();
+
+        let mut tree = Self {
+            processes,
+            children,
+            roots,
+            rollups: HashMap::new(),
+        };
+        tree.compute_rollups();
+        tree
+    }
+
+    /// Collect a process and all its descendants
+    fn collect_descendants(&self, pid: u32, out: &mut HashSet<u32>) {
+        let mut stack = vec![pid];
+        while let Some(current) = stack.pop() {
+            if out.insert(current) {
+                stack.extend(self.children(current));
+            }
+        }
+    }
+
+    /// Compute CPU/memory rollups for every subtree (post-order)
+    fn compute_rollups(&mut self) {
+        let mut rollups: HashMap<u32, SubtreeRollup> = HashMap::new();
+
+        for &root in &self.roots {
+            // Iterative post-order traversal to avoid deep recursion
+            let mut stack = vec![(root, false)];
+            while let Some((pid, visited)) = stack.pop() {
+                if visited {
+                    let process = &self.processes[&pid];
+                    let mut rollup = SubtreeRollup {
+                        cpu_percent: process.cpu_percent,
+                        memory_rss: process.memory_rss,
+                        process_count: 1,
+                    };
+                    for child in self.children(pid) {
+                        if let Some(child_rollup) = rollups.get(child) {
+                            rollup.cpu_percent += child_rollup.cpu_percent;
+                            rollup.memory_rss += child_rollup.memory_rss;
+                            rollup.process_count += child_rollup.process_count;
+                        }
+                    }
+                    rollups.insert(pid, rollup);
+                } else {
+                    stack.push((pid, true));
+                    for &child in self.children(pid) {
+                        stack.push((child, false));
+                    }
+                }
+            }
+        }
+
+        self.rollups = rollups;
+    }
+
+    /// Get process info by PID
+    pub fn get(&self, pid: u32) -> Option<&ProcessInfo> {
+        self.processes.get(&pid)
+    }
+
+    /// Get direct children of a process
+    pub fn children(&self, pid: u32) -> &[u32] {
+        self.children.get(&pid).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get parent PID if the parent is part of this tree
+    pub fn parent(&self, pid: u32) -> Option<u32> {
+        let ppid = self.processes.get(&pid)?.ppid;
+        if ppid != pid && self.processes.contains_key(&ppid) {
+            Some(ppid)
+        } else {
+            None
+        }
+    }
+
+    /// Get the rollup for a subtree
+    pub fn rollup(&self, pid: u32) -> SubtreeRollup {
+        self.rollups.get(&pid).copied().unwrap_or_default()
+    }
+
+    /// Number of processes in the tree
+    pub fn process_count(&self) -> usize {
+        self.processes.len()
+    }
+
+    /// Flatten the tree into visible rows, skipping collapsed subtrees
+    pub fn flatten(&self, collapsed: &HashSet<u32>) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        for &root in &self.roots {
+            self.flatten_into(root, None, true, collapsed, &mut rows);
+        }
+        rows
+    }
+
+    /// Push a row for `pid` and, if expanded, its children
+    ///
+    /// `guide` is the accumulated guide string of the parent, `None` for roots.
+    fn flatten_into(
+        &self,
+        pid: u32,
+        guide: Option<&str>,
+        is_last: bool,
+        collapsed: &HashSet<u32>,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        let children = self.children(pid);
+        let expanded = !collapsed.contains(&pid);
+
+        let prefix = match guide {
+            None => String::new(),
+            Some(g) if is_last => format!("{}└─ ", g),
+            Some(g) => format!("{}├─ ", g),
+        };
+
+        rows.push(TreeRow {
+            pid,
+            prefix,
+            has_children: !children.is_empty(),
+            expanded,
+        });
+
+        if !expanded {
+            return;
+        }
+
+        let child_guide = match guide {
+            None => String::new(),
+            Some(g) if is_last => format!("{}   ", g),
+            Some(g) => format!("{}│  ", g),
+        };
+
+        for (idx, &child) in children.iter().enumerate() {
+            let last = idx + 1 == children.len();
+            self.flatten_into(child, Some(&child_guide), last, collapsed, rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::proctrace::supervisor::Supervisor;
+    use crate::modules::proctrace::users::IdSet;
+
+    fn process(pid: u32, ppid: u32, cpu_percent: f32, memory_rss: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            name: format!("p{}", pid),
+            cmdline: Vec::new(),
+            state: 'S',
+            session: 0,
+            user: "root".to_string(),
+            uids: IdSet::default(),
+            gids: IdSet::default(),
+            cpu_percent,
+            memory_rss,
+            start_time: 0,
+            start_ticks: 0,
+            supervisor: Supervisor::Unknown,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 1 ─┬─ 10 ─┬─ 11
+    ///    │      └─ 12 ── 13
+    ///    └─ 20
+    /// 50 (parent 49 is gone)
+    fn sample() -> Vec<ProcessInfo> {
+        vec![
+            process(13, 12, 0.5, 50),
+            process(20, 1, 4.0, 400),
+            process(1, 0, 0.0, 10),
+            process(12, 10, 3.0, 300),
+            process(50, 49, 1.0, 1000),
+            process(11, 10, 2.0, 200),
+            process(10, 1, 1.0, 100),
+        ]
+    }
+
+    fn rows(tree: &ProcessTree, collapsed: &[u32]) -> Vec<(u32, String)> {
+        tree.flatten(&collapsed.iter().copied().collect())
+            .into_iter()
+            .map(|row| (row.pid, row.prefix))
+            .collect()
+    }
+
+    #[test]
+    fn build_links_children_and_roots() {
+        let tree = ProcessTree::build(sample());
+
+        assert_eq!(tree.process_count(), 7);
+        assert_eq!(tree.children(1), [10, 20]);
+        assert_eq!(tree.children(10), [11, 12]);
+        assert_eq!(tree.children(13), [] as [u32; 0]);
+        assert_eq!(tree.parent(13), Some(12));
+        assert_eq!(tree.parent(1), None);
+        // Orphans whose parent is not in the snapshot become roots
+        assert_eq!(tree.parent(50), None);
+        assert_eq!(tree.roots, [1, 50]);
+    }
+
+    #[test]
+    fn rollups_sum_subtrees() {
+        let tree = ProcessTree::build(sample());
+
+        let rollup = tree.rollup(10);
+        assert_eq!(rollup.process_count, 4);
+        assert_eq!(rollup.memory_rss, 100 + 200 + 300 + 50);
+        assert!((rollup.cpu_percent - 6.5).abs() < f32::EPSILON);
+
+        let root = tree.rollup(1);
+        assert_eq!(root.process_count, 6);
+        assert_eq!(root.memory_rss, 10 + 650 + 400);
+
+        let leaf = tree.rollup(13);
+        assert_eq!(leaf.process_count, 1);
+        assert_eq!(leaf.memory_rss, 50);
+
+        assert_eq!(tree.rollup(999).process_count, 0);
+    }
+
+    #[test]
+    fn flatten_orders_rows_depth_first_with_guides() {
+        let tree = ProcessTree::build(sample());
+
+        assert_eq!(
+            rows(&tree, &[]),
+            [
+                (1, "".to_string()),
+                (10, "├─ ".to_string()),
+                (11, "│  ├─ ".to_string()),
+                (12, "│  └─ ".to_string()),
+                (13, "│     └─ ".to_string()),
+                (20, "└─ ".to_string()),
+                (50, "".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn flatten_skips_collapsed_subtrees() {
+        let tree = ProcessTree::build(sample());
+
+        let pids: Vec<u32> = rows(&tree, &[10]).into_iter().map(|(pid, _)| pid).collect();
+        assert_eq!(pids, [1, 10, 20, 50]);
+
+        let flat = tree.flatten(&HashSet::from([10]));
+        assert!(flat[1].has_children && !flat[1].expanded);
+        assert!(!flat[2].has_children);
+
+        let pids: Vec<u32> = rows(&tree, &[1]).into_iter().map(|(pid, _)| pid).collect();
+        assert_eq!(pids, [1, 50]);
+    }
+
+    #[test]
+    fn build_subtrees_folds_nested_roots() {
+        // 12 lies under 10 and is not listed twice; 999 does not exist
+        let tree = ProcessTree::build_subtrees(sample(), &[12, 20, 10, 999]);
+
+        assert_eq!(tree.roots, [10, 20]);
+        assert_eq!(tree.process_count(), 5);
+        assert!(tree.get(1).is_none());
+        assert_eq!(tree.parent(10), None);
+        assert_eq!(tree.rollup(10).process_count, 4);
+
+        assert_eq!(
+            rows(&tree, &[]),
+            [
+                (10, "".to_string()),
+                (11, "├─ ".to_string()),
+                (12, "└─ ".to_string()),
+                (13, "   └─ ".to_string()),
+                (20, "".to_string()),
+            ]
+        );
+    }
+}
@@ -1,13 +1,26 @@
-use super::collector::Supervisor;
-use super::network::{ConnectionState, Protocol};
-use super::state::{InputMode, ProcessTracerState};
+use super::actions::Signal;
+use super::collector::{format_bytes, is_public_listener};
+use super::env::{EnvChange, diff_environment, display_value, is_secret_key};
+use super::explain::explain;
+use super::files::FdKind;
+use super::journal::PRIORITY_NAMES;
+use super::memory::HIGH_OOM_SCORE;
+use super::network::{
+    ConnectionState, Protocol, count_connection_states, format_socket_addr, summarize_connections,
+};
+use super::query::{HEALTH_KINDS, QueryResult, SortKey};
+use super::security::{ResourceLimit, SeccompMode, describe_capabilities};
+use super::state::{DetailsTab, InputMode, ProcessTracerState};
+use super::supervisor::{Supervisor, supervisor_details, supervisor_label};
+use super::threads::state_description;
+use super::users::{group_name, user_name};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, BorderType, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        Block, BorderType, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
         ScrollbarState, StatefulWidget, Widget,
     },
 };
@@ -17,6 +30,23 @@ pub fn render(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
     match state.input_mode {
         InputMode::Query => render_query_mode(state, area, buf),
         InputMode::ViewResults => render_results_mode(state, area, buf),
+        InputMode::Tree => render_tree_mode(state, area, buf),
+        InputMode::Audit => render_audit_mode(state, area, buf),
+        InputMode::Ports => render_ports_mode(state, area, buf),
+        InputMode::Signal => {
+            render_results_mode(state, area, buf);
+            render_signal_picker(state, area, buf);
+        }
+        InputMode::Renice => {
+            render_results_mode(state, area, buf);
+            render_renice_prompt(state, area, buf);
+        }
+        InputMode::Confirm => {
+            render_results_mode(state, area, buf);
+            render_confirm_dialog(state, area, buf);
+        }
+        InputMode::Journal => render_journal_mode(state, area, buf),
+        InputMode::Health => render_health_mode(state, area, buf),
     }
 
     // Render notification if present
@@ -62,7 +92,7 @@ fn render_query_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
     input.render(chunks[1], buf);
 
     // Render cursor
-    let cursor_x = chunks[1].x + 1 + state.query_input.len() as u16 + 1;
+    let cursor_x = chunks[1].x + 1 + state.query_input.chars().count() as u16 + 1;
     let cursor_y = chunks[1].y + 1;
     if cursor_x < chunks[1].x + chunks[1].width - 1 {
         if let Some(cell) = buf.cell_mut((cursor_x, cursor_y)) {
@@ -71,6 +101,24 @@ fn render_query_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
         }
     }
 
+    // Point at the position of a parse error
+    if let Some(ref error) = state.query_error {
+        let error_line = Line::from(vec![
+            Span::raw(" ".repeat(error.position + 1)),
+            Span::styled(
+                format!("^ {}", error.message),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        let error_area = Rect {
+            x: chunks[1].x + 1,
+            y: chunks[1].y + 2,
+            width: chunks[1].width.saturating_sub(2),
+            height: 1,
+        };
+        Paragraph::new(error_line).render(error_area, buf);
+    }
+
     // Help text
     let help_lines = vec![
         Line::from(vec![Span::styled(
@@ -93,9 +141,82 @@ fn render_query_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
         Line::from(vec![
             Span::styled("  • ", Style::default().fg(Color::Green)),
             Span::styled("Port: ", Style::default().fg(Color::Yellow)),
-            Span::raw(":8080"),
+            Span::raw(":8080  :80,443  :8000-8100"),
             Span::styled(" (may need root)", Style::default().fg(Color::DarkGray)),
         ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Unix socket: ", Style::default().fg(Color::Yellow)),
+            Span::raw("@/run/docker.sock"),
+            Span::styled(" or ", Style::default().fg(Color::DarkGray)),
+            Span::raw("unix:postgres"),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Open file: ", Style::default().fg(Color::Yellow)),
+            Span::raw("file:/var/log/app.log"),
+            Span::styled(
+                " (holders, incl. deleted)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Fields: ", Style::default().fg(Color::Yellow)),
+            Span::raw("user: cmd: cwd: env:KEY=VAL unit: container: warn: state:Z"),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Metrics: ", Style::default().fg(Color::Yellow)),
+            Span::raw("cpu>50 mem>1G uptime>7d"),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("After upgrades: ", Style::default().fg(Color::Yellow)),
+            Span::raw("needs-restart"),
+            Span::styled(
+                " (deleted/replaced binaries or libraries)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Combine: ", Style::default().fg(Color::Yellow)),
+            Span::raw("user:root AND (cpu>50 OR NOT unit:nginx)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Process tree: ", Style::default().fg(Color::Yellow)),
+            Span::raw("Ctrl+T"),
+            Span::styled(" (all processes)", Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Port overview: ", Style::default().fg(Color::Yellow)),
+            Span::raw("Ctrl+P"),
+            Span::styled(
+                " (all listeners by port)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Exposure audit: ", Style::default().fg(Color::Yellow)),
+            Span::raw("Ctrl+A"),
+            Span::styled(
+                " (public listeners system-wide)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Green)),
+            Span::styled("Health overview: ", Style::default().fg(Color::Yellow)),
+            Span::raw("Ctrl+S"),
+            Span::styled(
+                " (zombies, D-state, stopped, orphans)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Note: ", Style::default().fg(Color::Yellow)),
@@ -119,7 +240,7 @@ fn render_query_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
     help.render(chunks[2], buf);
 
     // Status bar
-    let status_text = "[Enter] Search  [↑/↓] History  [Esc] Exit";
+    let status_text = "[Enter] Search  [↑/↓] History  [Ctrl+T] Tree  [Ctrl+A] Audit  [Ctrl+P] Ports  [Ctrl+S] Health  [Esc] Exit";
     let status = Paragraph::new(status_text)
         .block(
             Block::bordered()
@@ -145,10 +266,22 @@ fn render_results_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer)
 
     // Header
     let result_count = state.query_results.len();
+    let watch_str = if state.watch_mode {
+        format!("[watch {}s] ", state.watch_interval.as_secs())
+    } else {
+        String::new()
+    };
+    let sort_str = if state.sort_key == SortKey::Default {
+        String::new()
+    } else {
+        format!("[sorted by {}] ", state.sort_key.label())
+    };
     let title = format!(
-        " Results: {} match{} ",
+        " Results: {} match{} {}{}",
         result_count,
-        if result_count == 1 { "" } else { "es" }
+        if result_count == 1 { "" } else { "es" },
+        watch_str,
+        sort_str
     );
     let header = Block::bordered()
         .title(title)
@@ -164,7 +297,7 @@ fn render_results_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer)
     render_detailed_analysis(state, chunks[2], buf);
 
     // Status bar
-    let status_text = "[j/k] Navigate  [PageUp/Down] Scroll  [/] New Query  [Esc] Back";
+    let status_text = "[j/k] Navigate  [PageUp/Down] Scroll  [t/T] Tree  [a] Audit  [o] Ports  [H] Health  [Tab] Details tab  [w] Watch  [O] Sort  [v] Reveal secrets  [d] Env diff  [e] Export why  [x/X] Export  [y/Y] Copy  [f] Format  [J] Journal  [s] Signal  [n] Renice  [R/S] Restart/Stop unit  [/] New Query  [Esc] Back";
     let status = Paragraph::new(status_text)
         .block(
             Block::bordered()
@@ -176,230 +309,1314 @@ fn render_results_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer)
     status.render(chunks[3], buf);
 }
 
-/// Render results list
-fn render_results_list(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
-    if state.query_results.is_empty() {
-        let msg = Paragraph::new("No processes found")
+/// Render tree mode (collapsible process tree)
+fn render_tree_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Tree
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+
+    // Header
+    let process_count = state.tree.as_ref().map_or(0, |t| t.process_count());
+    let header = Block::bordered()
+        .title(format!(" Process Tree: {} processes ", process_count))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(Color::Cyan));
+    header.render(chunks[0], buf);
+
+    // Tree rows
+    if let Some(ref tree) = state.tree {
+        let items: Vec<ListItem> = state
+            .tree_rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, row)| {
+                let proc = tree.get(row.pid)?;
+                let rollup = tree.rollup(row.pid);
+                let is_selected = idx == state.tree_selected;
+
+                let marker = match (row.has_children, row.expanded) {
+                    (false, _) => "  ",
+                    (true, true) => "▾ ",
+                    (true, false) => "▸ ",
+                };
+
+                let subtree_str = if rollup.process_count > 1 {
+                    format!(
+                        "  Σ {:.1}% CPU  {}  [{} procs]",
+                        rollup.cpu_percent,
+                        format_bytes(rollup.memory_rss),
+                        rollup.process_count
+                    )
+                } else {
+                    format!(
+                        "  {:.1}% CPU  {}",
+                        rollup.cpu_percent,
+                        format_bytes(rollup.memory_rss)
+                    )
+                };
+
+                let line = Line::from(vec![
+                    Span::styled(&row.prefix, Style::default().fg(Color::DarkGray)),
+                    Span::styled(marker, Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        &proc.name,
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(if is_selected {
+                                Modifier::BOLD
+                            } else {
+                                Modifier::empty()
+                            }),
+                    ),
+                    Span::styled(
+                        format!(" ({})", proc.pid),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(subtree_str, Style::default().fg(Color::Gray)),
+                ]);
+
+                Some(ListItem::new(line))
+            })
+            .collect();
+
+        let list = List::new(items)
             .block(
                 Block::bordered()
-                    .title(" Results ")
+                    .title(" Tree ")
                     .border_type(BorderType::Rounded),
             )
-            .centered()
-            .style(Style::default().fg(Color::DarkGray));
-        msg.render(area, buf);
-        return;
+            .highlight_style(Style::default().bg(Color::DarkGray));
+
+        let mut list_state = ListState::default().with_selected(Some(state.tree_selected));
+        StatefulWidget::render(list, chunks[1], buf, &mut list_state);
     }
 
-    let items: Vec<ListItem> = state
-        .query_results
+    // Status bar
+    let status_text =
+        "[j/k] Navigate  [h/l] Collapse/Expand  [p/c] Parent/Child  [Enter] Details  [Esc] Back";
+    let status = Paragraph::new(status_text)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .centered()
+        .style(Style::default().fg(Color::Gray));
+    status.render(chunks[2], buf);
+}
+
+/// Render journal mode (journal lines of the selected unit or process)
+fn render_journal_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let Some(ref journal) = state.journal else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Journal lines
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+
+    // Header
+    let priority_str = if journal.max_priority == 7 {
+        "all priorities".to_string()
+    } else {
+        format!(
+            "{} and above",
+            PRIORITY_NAMES[journal.max_priority as usize]
+        )
+    };
+    let header = Block::bordered()
+        .title(format!(
+            " Journal: {}  ({} lines, {}){} ",
+            journal.source.describe(),
+            journal.entries.len(),
+            priority_str,
+            if journal.follow { "  [follow]" } else { "" }
+        ))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(Color::Cyan));
+    header.render(chunks[0], buf);
+
+    // Journal lines, anchored to the bottom like `journalctl -e`
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let end = journal.entries.len().saturating_sub(journal.scroll);
+    let start = end.saturating_sub(visible);
+
+    let lines: Vec<Line> = journal.entries[start..end]
         .iter()
-        .enumerate()
-        .map(|(idx, result)| {
-            let proc = &result.process;
-            let supervisor_str = match &proc.supervisor {
-                Supervisor::Systemd { unit } => format!("systemd: {}", unit),
-                Supervisor::Docker { container_id } => format!("docker: {}", container_id),
-                Supervisor::Shell => "shell".to_string(),
-                Supervisor::Unknown => "unknown".to_string(),
+        .map(|entry| {
+            let style = match entry.priority {
+                Some(0..=3) => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Some(4) => Style::default().fg(Color::Yellow),
+                Some(5) => Style::default().add_modifier(Modifier::BOLD),
+                Some(7) => Style::default().fg(Color::DarkGray),
+                _ => Style::default(),
+            };
+            let source = match (&entry.identifier, entry.pid) {
+                (Some(ident), Some(pid)) => format!("{}[{}]: ", ident, pid),
+                (Some(ident), None) => format!("{}: ", ident),
+                (None, _) => String::new(),
             };
 
-            let is_selected = idx == state.selected_result;
-            let symbol = if is_selected { "▶ " } else { "  " };
-
-            let line = Line::from(vec![
-                Span::raw(symbol),
+            Line::from(vec![
                 Span::styled(
-                    &proc.name,
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(if is_selected {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
+                    format!("{} ", entry.time_str()),
+                    Style::default().fg(Color::DarkGray),
                 ),
-                Span::raw(format!(" (PID {}) - ", proc.pid)),
-                Span::styled(supervisor_str, Style::default().fg(Color::Cyan)),
-            ]);
-
-            ListItem::new(line).style(if is_selected {
-                Style::default().bg(Color::DarkGray)
-            } else {
-                Style::default()
-            })
+                Span::styled(source, Style::default().fg(Color::Cyan)),
+                Span::styled(&entry.message, style),
+            ])
         })
         .collect();
 
-    let list = List::new(items).block(
-        Block::bordered()
-            .title(" Results ")
-            .border_type(BorderType::Rounded),
-    );
-
-    Widget::render(list, area, buf);
-}
-
-/// Render detailed analysis panel
-fn render_detailed_analysis(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
-    let result = match state.get_selected_result() {
-        Some(r) => r,
-        None => {
-            let msg = Paragraph::new("No result selected")
-                .block(
-                    Block::bordered()
-                        .title(" Details ")
-                        .border_type(BorderType::Rounded),
-                )
-                .centered()
-                .style(Style::default().fg(Color::DarkGray));
-            msg.render(area, buf);
-            return;
-        }
+    let title = if journal.entries.is_empty() {
+        " No journal entries ".to_string()
+    } else {
+        format!(" Lines {}-{} of {} ", start + 1, end, journal.entries.len())
     };
+    Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(title)
+                .border_type(BorderType::Rounded),
+        )
+        .render(chunks[1], buf);
 
-    let mut lines = Vec::new();
+    // Status bar
+    let status_text = "[j/k] Scroll  [g/G] Top/Bottom  [p] Priority  [f] Follow  [m] More lines  [r] Reload  [Esc] Back";
+    let status = Paragraph::new(status_text)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .centered()
+        .style(Style::default().fg(Color::Gray));
+    status.render(chunks[2], buf);
+}
 
-    // === PROCESS ===
-    lines.push(Line::from(vec![Span::styled(
-        "=== PROCESS ===",
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    )]));
-    lines.push(Line::from(""));
+/// Render audit mode (publicly bound listeners)
+fn render_audit_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Listener table
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
 
-    lines.push(Line::from(vec![
-        Span::styled("Name:    ", Style::default().fg(Color::Cyan)),
-        Span::raw(&result.process.name),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("PID:     ", Style::default().fg(Color::Cyan)),
-        Span::raw(result.process.pid.to_string()),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("User:    ", Style::default().fg(Color::Cyan)),
-        Span::raw(&result.process.user),
-    ]));
+    // Header
+    let header = Block::bordered()
+        .title(format!(
+            " Exposure Audit: {} public listener{} ",
+            state.audit_entries.len(),
+            if state.audit_entries.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(Color::Red));
+    header.render(chunks[0], buf);
 
-    let cmdline = if result.process.cmdline.is_empty() {
-        format!("[{}]", result.process.name)
+    // Listener table
+    if state.audit_entries.is_empty() {
+        let msg = Paragraph::new("Nothing is listening on a public address")
+            .block(
+                Block::bordered()
+                    .title(" Listeners ")
+                    .border_type(BorderType::Rounded),
+            )
+            .centered()
+            .style(Style::default().fg(Color::Green));
+        msg.render(chunks[1], buf);
     } else {
-        result.process.cmdline.join(" ")
-    };
-    lines.push(Line::from(vec![
-        Span::styled("Command: ", Style::default().fg(Color::Cyan)),
-        Span::raw(&cmdline),
-    ]));
+        let mut items = vec![ListItem::new(Line::from(Span::styled(
+            format!(
+                "{:<5} {:<24} {:>7}  {:<16} {:<10} {}",
+                "PROTO", "ADDRESS", "PID", "PROCESS", "USER", "SUPERVISOR"
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )))];
+
+        items.extend(state.audit_entries.iter().map(|entry| {
+            let addr = format_socket_addr(&entry.binding.local_addr, entry.binding.local_port);
+            let (pid, name, user, supervisor) = match entry.process {
+                Some(ref p) => (
+                    p.pid.to_string(),
+                    p.name.clone(),
+                    p.user.clone(),
+                    supervisor_label(&p.supervisor),
+                ),
+                None => (
+                    "?".to_string(),
+                    "(not visible)".to_string(),
+                    "?".to_string(),
+                    String::new(),
+                ),
+            };
 
-    if let Some(ref cwd) = result.working_directory {
-        lines.push(Line::from(vec![
-            Span::styled("CWD:     ", Style::default().fg(Color::Cyan)),
-            Span::raw(cwd),
-        ]));
-    }
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<5} ", entry.binding.protocol.as_str()),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(format!("{:<24} ", addr), Style::default().fg(Color::Red)),
+                Span::styled(format!("{:>7}  ", pid), Style::default().fg(Color::Green)),
+                Span::styled(format!("{:<16} ", name), Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{:<10} ", user)),
+                Span::styled(supervisor, Style::default().fg(Color::Cyan)),
+            ]))
+        }));
+
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(" Listeners on 0.0.0.0 / :: ")
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray));
 
-    lines.push(Line::from(vec![
-        Span::styled("Uptime:  ", Style::default().fg(Color::Cyan)),
-        Span::raw(result.process.uptime_str()),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("Memory:  ", Style::default().fg(Color::Cyan)),
-        Span::raw(result.process.memory_str()),
-    ]));
+        // Offset by one for the header row
+        let mut list_state = ListState::default().with_selected(Some(state.audit_selected + 1));
+        StatefulWidget::render(list, chunks[1], buf, &mut list_state);
+    }
+
+    // Status bar
+    let status_text = "[j/k] Navigate  [Enter] Details  [r] Rescan  [Esc] Back";
+    let status = Paragraph::new(status_text)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .centered()
+        .style(Style::default().fg(Color::Gray));
+    status.render(chunks[2], buf);
+}
+
+/// Render health mode (zombie, D-state, stopped and orphaned processes)
+fn render_health_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Process table
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+
+    let Some(report) = state.health.as_ref() else {
+        return;
+    };
+
+    // Header with counts per kind
+    let labels = ["Zombies", "D-state", "Stopped", "Orphans"];
+    let counts: Vec<String> = HEALTH_KINDS
+        .iter()
+        .zip(labels)
+        .map(|(kind, label)| format!("{} {}", label, report.count(kind)))
+        .collect();
+    let header_color = if report.entries.is_empty() {
+        Color::Green
+    } else {
+        Color::Yellow
+    };
+    let header = Block::bordered()
+        .title(format!(
+            " System Health: {} of {} processes ",
+            counts.join(" · "),
+            report.total_processes
+        ))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(header_color));
+    header.render(chunks[0], buf);
+
+    // Process table
+    if report.entries.is_empty() {
+        let msg = Paragraph::new("No zombie, D-state, stopped or orphaned processes")
+            .block(
+                Block::bordered()
+                    .title(" Processes ")
+                    .border_type(BorderType::Rounded),
+            )
+            .centered()
+            .style(Style::default().fg(Color::Green));
+        msg.render(chunks[1], buf);
+    } else {
+        let mut items = vec![ListItem::new(Line::from(Span::styled(
+            format!(
+                "{:<11} {:>7}  {:<16} {:<10} {}",
+                "STATE", "PID", "PROCESS", "USER", "DETAILS"
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )))];
+
+        items.extend(report.entries.iter().map(|entry| {
+            let p = &entry.process;
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<11} ", entry.warning.symbol()),
+                    Style::default().fg(entry.warning.color()),
+                ),
+                Span::styled(format!("{:>7}  ", p.pid), Style::default().fg(Color::Green)),
+                Span::styled(
+                    format!("{:<16} ", p.name),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!("{:<10} ", p.user)),
+                Span::styled(
+                    entry.warning.description(),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]))
+        }));
+
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(" Processes needing attention ")
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray));
+
+        // Offset by one for the header row
+        let mut list_state = ListState::default().with_selected(Some(state.health_selected + 1));
+        StatefulWidget::render(list, chunks[1], buf, &mut list_state);
+    }
+
+    // Status bar
+    let status_text = "[j/k] Navigate  [Enter] Details  [p] Parent details  [r] Rescan  [Esc] Back";
+    let status = Paragraph::new(status_text)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .centered()
+        .style(Style::default().fg(Color::Gray));
+    status.render(chunks[2], buf);
+}
+
+/// Render ports mode (listeners grouped by port)
+fn render_ports_mode(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Port table
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+
+    // Header
+    let header = Block::bordered()
+        .title(format!(
+            " Port Overview: {} listening port{} ",
+            state.port_groups.len(),
+            if state.port_groups.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(Color::Cyan));
+    header.render(chunks[0], buf);
+
+    // Port table
+    let mut items = vec![ListItem::new(Line::from(Span::styled(
+        format!(
+            "{:>6} {:<5} {:<28} {}",
+            "PORT", "PROTO", "ADDRESSES", "PROCESSES"
+        ),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )))];
+
+    items.extend(state.port_groups.iter().map(|group| {
+        let mut addrs: Vec<String> = group
+            .bindings
+            .iter()
+            .map(|b| match b.local_addr {
+                std::net::IpAddr::V6(v6) => format!("[{}]", v6),
+                std::net::IpAddr::V4(v4) => v4.to_string(),
+            })
+            .collect();
+        addrs.dedup();
+
+        let mut owners: Vec<String> = group
+            .processes
+            .iter()
+            .map(|p| format!("{}({})", p.name, p.pid))
+            .collect();
+        if group.hidden_owners {
+            owners.push("?".to_string());
+        }
+
+        let is_public = group.bindings.iter().any(is_public_listener);
+
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                format!("{:>6} ", group.port),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("{:<5} ", group.protocol.as_str()),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(
+                format!("{:<28} ", addrs.join(",")),
+                Style::default().fg(if is_public { Color::Red } else { Color::Gray }),
+            ),
+            Span::styled(owners.join(" "), Style::default().fg(Color::Green)),
+        ]))
+    }));
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(" Listeners ")
+                .border_type(BorderType::Rounded),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+
+    // Offset by one for the header row
+    let mut list_state = ListState::default().with_selected(Some(state.ports_selected + 1));
+    StatefulWidget::render(list, chunks[1], buf, &mut list_state);
+
+    // Status bar
+    let status_text = "[j/k] Navigate  [Enter] Show owners  [r] Rescan  [Esc] Back";
+    let status = Paragraph::new(status_text)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .centered()
+        .style(Style::default().fg(Color::Gray));
+    status.render(chunks[2], buf);
+}
+
+/// Greedy word wrap for prose in the details panel (which does not wrap)
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed = current.chars().count() + word.chars().count() + 1;
+        if !current.is_empty() && needed > width.max(20) {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Render results list
+fn render_results_list(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    if state.query_results.is_empty() {
+        let msg = Paragraph::new("No processes found")
+            .block(
+                Block::bordered()
+                    .title(" Results ")
+                    .border_type(BorderType::Rounded),
+            )
+            .centered()
+            .style(Style::default().fg(Color::DarkGray));
+        msg.render(area, buf);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .query_results
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| {
+            let proc = &result.process;
+            let supervisor_str = supervisor_label(&proc.supervisor);
+
+            let is_selected = idx == state.selected_result;
+            let symbol = if is_selected { "▶ " } else { "  " };
+
+            // Highlight metrics that changed since the last watch refresh
+            let metric_style = |changed: bool| {
+                if changed {
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Gray)
+                }
+            };
+
+            let mut spans = vec![
+                Span::raw(symbol),
+                Span::styled(
+                    &proc.name,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+                Span::raw(format!(" (PID {}) ", proc.pid)),
+                Span::styled(
+                    format!("{:5.1}% ", proc.cpu_percent),
+                    metric_style(result.changed.cpu),
+                ),
+                Span::styled(
+                    format!("{:>9} ", proc.memory_str()),
+                    metric_style(result.changed.memory),
+                ),
+            ];
+
+            if let Some(value) = state.sort_key.display_value(result) {
+                spans.push(Span::styled(
+                    format!("{} ", value),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+
+            spans.push(Span::raw("- "));
+            spans.push(Span::styled(
+                supervisor_str,
+                Style::default().fg(Color::Cyan),
+            ));
+
+            if result.exited {
+                spans.push(Span::styled(
+                    " [exited]",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            let line = Line::from(spans);
+
+            ListItem::new(line).style(if is_selected {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            })
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::bordered()
+            .title(" Results ")
+            .border_type(BorderType::Rounded),
+    );
+
+    Widget::render(list, area, buf);
+}
+
+/// Render detailed analysis panel
+fn render_detailed_analysis(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let result = match state.get_selected_result() {
+        Some(r) => r,
+        None => {
+            let msg = Paragraph::new("No result selected")
+                .block(
+                    Block::bordered()
+                        .title(" Details ")
+                        .border_type(BorderType::Rounded),
+                )
+                .centered()
+                .style(Style::default().fg(Color::DarkGray));
+            msg.render(area, buf);
+            return;
+        }
+    };
+
+    let wrap_width = area.width.saturating_sub(4) as usize;
+    let lines = match state.details_tab {
+        DetailsTab::Overview => {
+            overview_lines(result, wrap_width, state.reveal_secrets, state.env_diff)
+        }
+        DetailsTab::Io => io_lines(result, state.watch_mode),
+        DetailsTab::Threads => thread_lines(result),
+    };
+
+    // Apply scroll offset
+    let total_lines = lines.len(); // Save before consuming
+    let visible_lines: Vec<Line> = lines
+        .into_iter()
+        .skip(state.scroll_offset as usize)
+        .collect();
+
+    let details = Paragraph::new(visible_lines).block(
+        Block::bordered()
+            .title(format!(
+                " Details - {} (PID {}) ",
+                result.process.name, result.process.pid
+            ))
+            .title(tab_titles(state.details_tab).right_aligned())
+            .border_type(BorderType::Rounded),
+    );
+
+    details.render(area, buf);
+
+    // Render scrollbar if needed
+    let visible_height = area.height.saturating_sub(2) as usize;
+    if total_lines > visible_height {
+        let mut scrollbar_state = ScrollbarState::default()
+            .content_length(total_lines)
+            .position(state.scroll_offset as usize);
+
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+
+        StatefulWidget::render(scrollbar, area, buf, &mut scrollbar_state);
+    }
+}
+
+/// Build the overview tab (narrative, process, network, files, systemd, ...)
+fn overview_lines(
+    result: &QueryResult,
+    wrap_width: usize,
+    reveal_secrets: bool,
+    env_diff: bool,
+) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+
+    // === WHY IS THIS RUNNING? ===
+    lines.push(Line::from(vec![Span::styled(
+        "=== WHY IS THIS RUNNING? ===",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.push(Line::from(""));
+
+    for (idx, sentence) in explain(result).iter().enumerate() {
+        let style = if idx == 0 {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        for line in wrap_text(sentence, wrap_width) {
+            lines.push(Line::from(Span::styled(line, style)));
+        }
+    }
+    lines.push(Line::from(""));
+
+    // === PROCESS ===
+    lines.push(Line::from(vec![Span::styled(
+        "=== PROCESS ===",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Name:    ", Style::default().fg(Color::Cyan)),
+        Span::raw(&result.process.name),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("PID:     ", Style::default().fg(Color::Cyan)),
+        Span::raw(result.process.pid.to_string()),
+    ]));
+    // Show every ID only when real/effective/saved differ (setuid, privilege drops)
+    let (uids, gids) = (&result.process.uids, &result.process.gids);
+    let id_style = |uniform: bool| {
+        if uniform {
+            Style::default()
+        } else {
+            Style::default().fg(Color::Magenta)
+        }
+    };
+    lines.push(Line::from(vec![
+        Span::styled("User:    ", Style::default().fg(Color::Cyan)),
+        Span::styled(uids.describe(user_name), id_style(uids.is_uniform())),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Group:   ", Style::default().fg(Color::Cyan)),
+        Span::styled(gids.describe(group_name), id_style(gids.is_uniform())),
+    ]));
+
+    let cmdline = if result.process.cmdline.is_empty() {
+        format!("[{}]", result.process.name)
+    } else {
+        result.process.cmdline.join(" ")
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Command: ", Style::default().fg(Color::Cyan)),
+        Span::raw(cmdline),
+    ]));
+
+    if let Some(ref cwd) = result.working_directory {
+        lines.push(Line::from(vec![
+            Span::styled("CWD:     ", Style::default().fg(Color::Cyan)),
+            Span::raw(cwd),
+        ]));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled("Uptime:  ", Style::default().fg(Color::Cyan)),
+        Span::raw(result.process.uptime_str()),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("CPU:     ", Style::default().fg(Color::Cyan)),
+        Span::raw(format!("{:.1}%", result.process.cpu_percent)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Memory:  ", Style::default().fg(Color::Cyan)),
+        Span::raw(result.process.memory_str()),
+    ]));
+    if result.exited {
+        lines.push(Line::from(vec![
+            Span::styled("Status:  ", Style::default().fg(Color::Cyan)),
+            Span::styled("exited", Style::default().fg(Color::Red)),
+        ]));
+    }
 
     lines.push(Line::from(""));
 
+    // === MEMORY ===
+    let memory = &result.memory;
+    if memory.pss.is_some() || memory.oom_score.is_some() || memory.cgroup.is_some() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== MEMORY ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        let label = |text| Span::styled(text, Style::default().fg(Color::Cyan));
+        lines.push(Line::from(vec![
+            label("RSS:       "),
+            Span::raw(result.process.memory_str()),
+        ]));
+        for (text, value) in [
+            ("PSS:       ", memory.pss),
+            ("USS:       ", memory.uss),
+            ("Swap:      ", memory.swap),
+        ] {
+            if let Some(bytes) = value {
+                lines.push(Line::from(vec![
+                    label(text),
+                    Span::raw(format_bytes(bytes)),
+                ]));
+            }
+        }
+
+        if let Some(score) = memory.oom_score {
+            let style = if score >= HIGH_OOM_SCORE {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let adj_str = memory
+                .oom_score_adj
+                .map(|adj| format!(" (adj {})", adj))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![
+                label("OOM score: "),
+                Span::styled(score.to_string(), style),
+                Span::styled(adj_str, Style::default().fg(Color::Gray)),
+            ]));
+        }
+
+        if let Some(ref cgroup) = memory.cgroup {
+            let (usage_str, style) = match (cgroup.limit, cgroup.usage_ratio()) {
+                (Some(limit), Some(ratio)) => (
+                    format!(
+                        "{} / {} ({:.0}%)",
+                        format_bytes(cgroup.current),
+                        format_bytes(limit),
+                        ratio * 100.0
+                    ),
+                    if cgroup.is_near_limit() {
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    },
+                ),
+                _ => (
+                    format!("{} (no limit)", format_bytes(cgroup.current)),
+                    Style::default(),
+                ),
+            };
+            lines.push(Line::from(vec![
+                label("Cgroup:    "),
+                Span::styled(usage_str, style),
+                Span::styled(
+                    format!("  {}", cgroup.path),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+
     // === ANCESTOR CHAIN ===
     if !result.ancestor_chain.is_empty() {
         lines.push(Line::from(vec![Span::styled(
-            "=== ANCESTOR CHAIN ===",
+            "=== ANCESTOR CHAIN ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for (idx, ancestor) in result.ancestor_chain.iter().enumerate() {
+            let supervisor_str = match &ancestor.supervisor {
+                Supervisor::Systemd { unit } => format!(" (Systemd: {})", unit),
+                Supervisor::Unknown => "".to_string(),
+                other => format!(" ({})", supervisor_label(other)),
+            };
+
+            let prefix = if idx == 0 {
+                "".to_string()
+            } else {
+                format!("{}", "  ".repeat(idx - 1) + "└─ ")
+            };
+
+            lines.push(Line::from(vec![
+                Span::raw(prefix),
+                Span::styled(
+                    format!("PID {}", ancestor.pid),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(" "),
+                Span::styled(&ancestor.name, Style::default().fg(Color::Yellow)),
+                Span::styled(supervisor_str, Style::default().fg(Color::Cyan)),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    // === SUPERVISOR ===
+    let supervisor_fields = supervisor_details(&result.process.supervisor);
+    if !supervisor_fields.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== SUPERVISOR ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for (label, value) in supervisor_fields {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", label), Style::default().fg(Color::Cyan)),
+                Span::raw(value),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    // === CONTAINER ===
+    let (container, pod) = match &result.process.supervisor {
+        Supervisor::Container(container) => (Some(container), None),
+        Supervisor::Kubernetes { container, pod } => (Some(container), Some(pod)),
+        _ => (None, None),
+    };
+    if let Some(container) = container {
+        lines.push(Line::from(vec![Span::styled(
+            "=== CONTAINER ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        let mut fields = vec![
+            ("Runtime:   ", Some(container.runtime.as_str().to_string())),
+            ("ID:        ", Some(container.short_id().to_string())),
+            ("Name:      ", container.name.clone()),
+            ("Image:     ", container.image.clone()),
+        ];
+        if let Some(pod) = pod {
+            fields.push(("Pod:       ", pod.name.clone()));
+            fields.push(("Namespace: ", pod.namespace.clone()));
+            fields.push(("Pod UID:   ", pod.uid.clone()));
+        }
+
+        for (label, value) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::Cyan)),
+                Span::raw(value),
+            ]));
+        }
+
+        if container.name.is_none() {
+            lines.push(Line::from(Span::styled(
+                "Runtime state not readable (name/image need root)",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    // === NETWORK ===
+    if !result.network_bindings.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== NETWORK ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for binding in &result.network_bindings {
+            let protocol_str = match binding.protocol {
+                Protocol::Tcp => "TCP",
+                Protocol::Udp => "UDP",
+                Protocol::Raw => "RAW",
+            };
+
+            let state_str = format!("[{}]", binding.state.as_str());
+
+            let queue_str = if binding.send_queue > 0 || binding.recv_queue > 0 {
+                format!(
+                    " (sendq {} recvq {})",
+                    binding.send_queue, binding.recv_queue
+                )
+            } else {
+                String::new()
+            };
+
+            let warning = if is_public_listener(binding) {
+                Span::styled(" ⚠ PUBLIC", Style::default().fg(Color::Red))
+            } else {
+                Span::raw("")
+            };
+
+            let remote_str = if let Some(ref remote_addr) = binding.remote_addr {
+                if let Some(remote_port) = binding.remote_port {
+                    format!(" → {}", format_socket_addr(remote_addr, remote_port))
+                } else {
+                    format!(" → {}", remote_addr)
+                }
+            } else {
+                String::new()
+            };
+
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "{} {} {}",
+                    protocol_str,
+                    format_socket_addr(&binding.local_addr, binding.local_port),
+                    state_str
+                )),
+                Span::raw(remote_str),
+                Span::styled(queue_str, Style::default().fg(Color::Magenta)),
+                warning,
+            ]));
+        }
+
+        lines.push(Line::from(""));
+
+        // === CONNECTIONS === (per-state / per-remote summary, to spot leaks)
+        let state_counts = count_connection_states(&result.network_bindings);
+        if !state_counts.is_empty() {
+            lines.push(Line::from(vec![Span::styled(
+                "=== CONNECTIONS ===",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            lines.push(Line::from(""));
+
+            let totals = state_counts
+                .iter()
+                .map(|(state, count)| format!("{} {}", count, state.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(Line::from(vec![
+                Span::styled("Total:  ", Style::default().fg(Color::Cyan)),
+                Span::raw(totals),
+            ]));
+
+            for summary in summarize_connections(&result.network_bindings)
+                .iter()
+                .take(10)
+            {
+                // Lingering half-closed sockets are the usual sign of a leak
+                let color = match summary.state {
+                    ConnectionState::CloseWait
+                    | ConnectionState::FinWait2
+                    | ConnectionState::LastAck => Color::Red,
+                    ConnectionState::Established => Color::Green,
+                    _ => Color::Gray,
+                };
+
+                let queue_str = if summary.send_queue > 0 || summary.recv_queue > 0 {
+                    format!(
+                        " (sendq {} recvq {})",
+                        summary.send_queue, summary.recv_queue
+                    )
+                } else {
+                    String::new()
+                };
+
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(summary.describe(), Style::default().fg(color)),
+                    Span::styled(queue_str, Style::default().fg(Color::Magenta)),
+                ]));
+            }
+
+            lines.push(Line::from(""));
+        }
+    }
+
+    // === UNIX SOCKETS ===
+    if !result.unix_sockets.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== UNIX SOCKETS ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for sock in &result.unix_sockets {
+            let path_str = sock.path.as_deref().unwrap_or("(unnamed)");
+            let state_str = if sock.listening {
+                "[LISTEN]"
+            } else if sock.connected {
+                "[CONNECTED]"
+            } else {
+                ""
+            };
+
+            let peer_span = match sock.peer {
+                Some(ref peer) => Span::styled(
+                    format!(" ↔ {} (PID {})", peer.name, peer.pid),
+                    Style::default().fg(Color::Green),
+                ),
+                None => Span::raw(""),
+            };
+
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "UNIX {} {} {}",
+                    sock.socket_type.as_str(),
+                    path_str,
+                    state_str
+                )),
+                peer_span,
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    // === OPEN FILES ===
+    if !result.open_files.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            format!("=== OPEN FILES ({}) ===", result.open_files.len()),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
+
+        for file in &result.open_files {
+            let mut spans = vec![
+                Span::styled(format!("{:>4} ", file.fd), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{:<6} ", file.kind.as_str()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(format!("{:<16} ", file.mode)),
+            ];
+
+            // Offsets are only meaningful for regular files
+            if file.kind == FdKind::File {
+                spans.push(Span::styled(
+                    format!("@{:<10} ", file.offset),
+                    Style::default().fg(Color::Gray),
+                ));
+            }
+
+            spans.push(Span::raw(file.target.clone()));
+
+            if file.deleted {
+                spans.push(Span::styled(
+                    " (deleted)",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+    }
+
+    // === SECURITY ===
+    let security = &result.security;
+    if security.cap_effective.is_some() || security.seccomp.is_some() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== SECURITY ===",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )]));
         lines.push(Line::from(""));
 
-        for (idx, ancestor) in result.ancestor_chain.iter().enumerate() {
-            let supervisor_str = match &ancestor.supervisor {
-                Supervisor::Systemd { unit } => format!(" (Systemd: {})", unit),
-                Supervisor::Docker { container_id } => format!(" (Docker: {})", container_id),
-                Supervisor::Shell => " (Shell)".to_string(),
-                Supervisor::Unknown => "".to_string(),
-            };
+        for (label, mask) in [
+            ("Cap (eff):   ", security.cap_effective),
+            ("Cap (perm):  ", security.cap_permitted),
+        ] {
+            if let Some(mask) = mask {
+                let style = if mask == 0 {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Magenta)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Cyan)),
+                    Span::styled(describe_capabilities(mask), style),
+                ]));
+            }
+        }
 
-            let prefix = if idx == 0 {
-                "".to_string()
+        if let Some(seccomp) = security.seccomp {
+            let style = if seccomp == SeccompMode::Disabled {
+                Style::default().fg(Color::Gray)
             } else {
-                format!("{}", "  ".repeat(idx - 1) + "└─ ")
+                Style::default().fg(Color::Green)
             };
+            lines.push(Line::from(vec![
+                Span::styled("Seccomp:     ", Style::default().fg(Color::Cyan)),
+                Span::styled(seccomp.as_str(), style),
+            ]));
+        }
 
+        if let Some(no_new_privs) = security.no_new_privs {
             lines.push(Line::from(vec![
-                Span::raw(prefix),
-                Span::styled(
-                    format!("PID {}", ancestor.pid),
-                    Style::default().fg(Color::Green),
-                ),
-                Span::raw(" "),
-                Span::styled(&ancestor.name, Style::default().fg(Color::Yellow)),
-                Span::styled(supervisor_str, Style::default().fg(Color::Cyan)),
+                Span::styled("NoNewPrivs:  ", Style::default().fg(Color::Cyan)),
+                Span::raw(if no_new_privs { "yes" } else { "no" }),
             ]));
         }
 
         lines.push(Line::from(""));
     }
 
-    // === NETWORK ===
-    if !result.network_bindings.is_empty() {
+    // === NAMESPACES ===
+    if !security.namespaces.is_empty() {
         lines.push(Line::from(vec![Span::styled(
-            "=== NETWORK ===",
+            "=== NAMESPACES ===",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )]));
         lines.push(Line::from(""));
 
-        for binding in &result.network_bindings {
-            let protocol_str = match binding.protocol {
-                Protocol::Tcp => "TCP",
-                Protocol::Udp => "UDP",
-            };
+        let isolated = security.isolated_namespaces();
+        if !isolated.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("Own {} namespace (differs from init)", isolated.join("+")),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
 
-            let state_str = match binding.state {
-                ConnectionState::Listen => "[LISTEN]",
-                ConnectionState::Established => "[ESTABLISHED]",
-                ConnectionState::TimeWait => "[TIME_WAIT]",
-                ConnectionState::CloseWait => "[CLOSE_WAIT]",
-                ConnectionState::Unknown => "[UNKNOWN]",
+        for ns in &security.namespaces {
+            let (marker, style) = match ns.differs_from_init {
+                Some(true) => ("  ≠ init", Style::default().fg(Color::Yellow)),
+                Some(false) => ("", Style::default().fg(Color::Gray)),
+                None => ("", Style::default()),
             };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<8}", ns.kind), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{}{}", ns.inode, marker), style),
+            ]));
+        }
 
-            let is_public = binding.local_addr.to_string() == "0.0.0.0"
-                || binding.local_addr.to_string() == "::";
+        lines.push(Line::from(""));
+    }
 
-            let warning = if is_public && binding.state == ConnectionState::Listen {
-                Span::styled(" ⚠ PUBLIC", Style::default().fg(Color::Red))
-            } else {
-                Span::raw("")
-            };
+    // === LIMITS ===
+    let limits: Vec<&ResourceLimit> = security
+        .limits
+        .iter()
+        .filter(|limit| limit.soft.is_some() || limit.usage.is_some())
+        .collect();
+    if !limits.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "=== LIMITS ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(""));
 
-            let remote_str = if let Some(ref remote_addr) = binding.remote_addr {
-                if let Some(remote_port) = binding.remote_port {
-                    format!(" → {}:{}", remote_addr, remote_port)
-                } else {
-                    format!(" → {}", remote_addr)
-                }
-            } else {
-                String::new()
-            };
+        let format_value = |value: Option<u64>, unit: &str| match value {
+            None => "unlimited".to_string(),
+            Some(v) if unit == "bytes" => format_bytes(v),
+            Some(v) => v.to_string(),
+        };
 
-            lines.push(Line::from(vec![
+        for limit in limits {
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<22}", limit.name),
+                    Style::default().fg(Color::Cyan),
+                ),
                 Span::raw(format!(
-                    "{} {}:{} {}",
-                    protocol_str, binding.local_addr, binding.local_port, state_str
+                    "{} / {}",
+                    format_value(limit.soft, &limit.unit),
+                    format_value(limit.hard, &limit.unit)
                 )),
-                Span::raw(remote_str),
-                warning,
-            ]));
+            ];
+
+            if let Some(usage) = limit.usage {
+                let style = if limit.is_near_limit() {
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                spans.push(Span::styled(
+                    format!("  (using {})", format_value(Some(usage), &limit.unit)),
+                    style,
+                ));
+            }
+
+            lines.push(Line::from(spans));
         }
 
         lines.push(Line::from(""));
@@ -447,27 +1664,200 @@ fn render_detailed_analysis(state: &ProcessTracerState, area: Rect, buf: &mut Bu
         }
 
         lines.push(Line::from(""));
+
+        // === UNIT DEPENDENCIES ===
+        let dependencies = [
+            ("TriggeredBy: ", &systemd.triggered_by),
+            ("WantedBy:    ", &systemd.wanted_by),
+            ("Requires:    ", &systemd.requires),
+            ("After:       ", &systemd.after),
+            ("PartOf:      ", &systemd.part_of),
+        ];
+        if dependencies.iter().any(|(_, units)| !units.is_empty()) {
+            lines.push(Line::from(vec![Span::styled(
+                "=== UNIT DEPENDENCIES ===",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            lines.push(Line::from(""));
+
+            for (label, units) in dependencies {
+                if units.is_empty() {
+                    continue;
+                }
+                let style = if label.starts_with("TriggeredBy") {
+                    Style::default().fg(Color::Magenta)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Cyan)),
+                    Span::styled(units.join(" "), style),
+                ]));
+            }
+
+            lines.push(Line::from(""));
+        }
+
+        // === UNIT RESOURCES ===
+        let mut resources: Vec<(&str, String, Style)> = Vec::new();
+        if let Some(memory) = systemd.memory_current {
+            resources.push(("Memory:      ", format_bytes(memory), Style::default()));
+        }
+        if let Some(nsec) = systemd.cpu_usage_nsec {
+            resources.push((
+                "CPU time:    ",
+                format!("{:.1}s", nsec as f64 / 1e9),
+                Style::default(),
+            ));
+        }
+        if let Some(tasks) = systemd.tasks_current {
+            resources.push(("Tasks:       ", tasks.to_string(), Style::default()));
+        }
+        if let Some(restarts) = systemd.n_restarts {
+            let style = if restarts > 0 {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            resources.push(("Restarts:    ", restarts.to_string(), style));
+        }
+        if let Some(ref last_exit) = systemd.last_exit {
+            let style = if last_exit.starts_with("exited, status=0") {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            resources.push(("Last exit:   ", last_exit.clone(), style));
+        }
+        if !resources.is_empty() {
+            lines.push(Line::from(vec![Span::styled(
+                "=== UNIT RESOURCES ===",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            lines.push(Line::from(""));
+
+            for (label, value, style) in resources {
+                lines.push(Line::from(vec![
+                    Span::styled(label, Style::default().fg(Color::Cyan)),
+                    Span::styled(value, style),
+                ]));
+            }
+
+            lines.push(Line::from(""));
+        }
+
+        // === UNIT FILES ===
+        if let Some(ref fragment) = systemd.fragment_path {
+            lines.push(Line::from(vec![Span::styled(
+                "=== UNIT FILES ===",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            lines.push(Line::from(""));
+
+            lines.push(Line::from(vec![
+                Span::styled("Fragment:    ", Style::default().fg(Color::Cyan)),
+                Span::raw(fragment),
+            ]));
+            for dropin in &systemd.drop_in_paths {
+                lines.push(Line::from(vec![
+                    Span::styled("Drop-in:     ", Style::default().fg(Color::Cyan)),
+                    Span::styled(dropin, Style::default().fg(Color::Magenta)),
+                ]));
+            }
+
+            lines.push(Line::from(""));
+        }
     }
 
     // === ENVIRONMENT ===
     if !result.environment.is_empty() {
-        lines.push(Line::from(vec![Span::styled(
-            "=== ENVIRONMENT ===",
+        let parent = result.parent();
+        let title = match parent {
+            Some(p) if env_diff => format!(
+                "=== ENVIRONMENT (diff vs parent {} PID {}) ===",
+                p.name, p.pid
+            ),
+            _ => "=== ENVIRONMENT ===".to_string(),
+        };
+        let masked = result
+            .environment
+            .keys()
+            .filter(|k| is_secret_key(k))
+            .count();
+        let mut header = vec![Span::styled(
+            title,
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        )]));
+        )];
+        if masked > 0 && !reveal_secrets {
+            header.push(Span::styled(
+                format!("  ({} masked, [v] to reveal)", masked),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(header));
         lines.push(Line::from(""));
 
-        let mut env_vars: Vec<_> = result.environment.iter().collect();
-        env_vars.sort_by_key(|(k, _)| *k);
-
-        for (key, value) in env_vars {
-            lines.push(Line::from(vec![
-                Span::styled(key, Style::default().fg(Color::Green)),
-                Span::raw("="),
-                Span::raw(value),
-            ]));
+        if env_diff {
+            match result.parent_environment.as_ref() {
+                Some(parent_env) => {
+                    let diff = diff_environment(&result.environment, parent_env);
+                    if diff.is_empty() {
+                        lines.push(Line::from(Span::styled(
+                            "Identical to the parent's environment",
+                            Style::default().fg(Color::DarkGray),
+                        )));
+                    }
+                    for entry in diff {
+                        let value = display_value(entry.key, entry.value, reveal_secrets);
+                        let mut spans = match entry.change {
+                            EnvChange::Added => {
+                                vec![Span::styled("+ ", Style::default().fg(Color::Green))]
+                            }
+                            EnvChange::Changed { .. } => {
+                                vec![Span::styled("~ ", Style::default().fg(Color::Yellow))]
+                            }
+                        };
+                        spans.push(Span::styled(entry.key, Style::default().fg(Color::Green)));
+                        spans.push(Span::raw("="));
+                        spans.push(Span::raw(value));
+                        if let EnvChange::Changed { parent_value } = &entry.change {
+                            spans.push(Span::styled(
+                                format!(
+                                    "  (parent: {})",
+                                    display_value(entry.key, parent_value, reveal_secrets)
+                                ),
+                                Style::default().fg(Color::DarkGray),
+                            ));
+                        }
+                        lines.push(Line::from(spans));
+                    }
+                }
+                None => {
+                    lines.push(Line::from(Span::styled(
+                        "Parent environment not readable (try sudo)",
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+        } else {
+            let mut env_vars: Vec<_> = result.environment.iter().collect();
+            env_vars.sort_by_key(|(k, _)| *k);
+
+            for (key, value) in env_vars {
+                lines.push(Line::from(vec![
+                    Span::styled(key, Style::default().fg(Color::Green)),
+                    Span::raw("="),
+                    Span::raw(display_value(key, value, reveal_secrets)),
+                ]));
+            }
         }
 
         lines.push(Line::from(""));
@@ -492,37 +1882,337 @@ fn render_detailed_analysis(state: &ProcessTracerState, area: Rect, buf: &mut Bu
         lines.push(Line::from(""));
     }
 
-    // Apply scroll offset
-    let total_lines = lines.len(); // Save before consuming
-    let visible_lines: Vec<Line> = lines
-        .into_iter()
-        .skip(state.scroll_offset as usize)
-        .collect();
+    lines
+}
 
-    let details = Paragraph::new(visible_lines).block(
-        Block::bordered()
-            .title(format!(
-                " Details - {} (PID {}) ",
-                result.process.name, result.process.pid
-            ))
-            .border_type(BorderType::Rounded),
-    );
+/// Tab bar for the details panel title ("Overview | I/O | Threads")
+fn tab_titles(active: DetailsTab) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (idx, tab) in DetailsTab::ALL.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+        }
+        let style = if *tab == active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        spans.push(Span::styled(tab.title(), style));
+    }
+    spans.push(Span::styled(
+        " [Tab] ",
+        Style::default().fg(Color::DarkGray),
+    ));
+    Line::from(spans)
+}
 
-    details.render(area, buf);
+/// Build the I/O tab from /proc/<pid>/io (rates need two samples in watch mode)
+fn io_lines(result: &QueryResult, watch_mode: bool) -> Vec<Line<'_>> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            "=== I/O ===",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
 
-    // Render scrollbar if needed
-    let visible_height = area.height.saturating_sub(2) as usize;
-    if total_lines > visible_height {
-        let mut scrollbar_state = ScrollbarState::default()
-            .content_length(total_lines)
-            .position(state.scroll_offset as usize);
+    let Some(ref io) = result.io else {
+        lines.push(Line::from(Span::styled(
+            "I/O counters not readable (try sudo)",
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    };
 
-        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓"));
+    let rates = result.io_rates;
+    let rate_span = |rate: Option<f64>, bytes: bool| match rate {
+        Some(r) if bytes => Span::styled(
+            format!("  {}/s", format_bytes(r as u64)),
+            Style::default().fg(if r > 0.0 { Color::Green } else { Color::Gray }),
+        ),
+        Some(r) => Span::styled(
+            format!("  {:.0}/s", r),
+            Style::default().fg(if r > 0.0 { Color::Green } else { Color::Gray }),
+        ),
+        None => Span::raw(""),
+    };
 
-        StatefulWidget::render(scrollbar, area, buf, &mut scrollbar_state);
+    let rows = [
+        (
+            "Read (disk):  ",
+            format_bytes(io.read_bytes),
+            rates.map(|r| r.read_bytes),
+            true,
+        ),
+        (
+            "Write (disk): ",
+            format_bytes(io.write_bytes),
+            rates.map(|r| r.write_bytes),
+            true,
+        ),
+        (
+            "Read (all):   ",
+            format_bytes(io.rchar),
+            rates.map(|r| r.rchar),
+            true,
+        ),
+        (
+            "Write (all):  ",
+            format_bytes(io.wchar),
+            rates.map(|r| r.wchar),
+            true,
+        ),
+        (
+            "Read calls:   ",
+            io.syscr.to_string(),
+            rates.map(|r| r.syscr),
+            false,
+        ),
+        (
+            "Write calls:  ",
+            io.syscw.to_string(),
+            rates.map(|r| r.syscw),
+            false,
+        ),
+    ];
+    for (label, total, rate, bytes) in rows {
+        lines.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{:>10}", total)),
+            rate_span(rate, bytes),
+        ]));
+    }
+
+    if io.cancelled_write_bytes > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("Cancelled:    ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{:>10}", format_bytes(io.cancelled_write_bytes))),
+            Span::styled(
+                "  (truncated before writeback)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let hint = if rates.is_some() {
+        "Rates since the previous refresh. \"all\" includes sockets, pipes and page cache hits."
+    } else if watch_mode {
+        "Rates appear after the next refresh."
+    } else {
+        "Enable watch mode [w] to see rates."
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    lines
+}
+
+/// Build the threads tab from /proc/<pid>/task
+fn thread_lines(result: &QueryResult) -> Vec<Line<'_>> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!("=== THREADS ({}) ===", result.threads.len()),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    if result.threads.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Threads not readable",
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    }
+
+    // Summary: threads per state (D-state threads are the usual "stuck" sign)
+    let mut counts: Vec<(char, usize)> = Vec::new();
+    for thread in &result.threads {
+        match counts.iter_mut().find(|(state, _)| *state == thread.state) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((thread.state, 1)),
+        }
+    }
+    let summary = counts
+        .iter()
+        .map(|(state, count)| format!("{} {}", count, state_description(*state)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    lines.push(Line::from(vec![
+        Span::styled("States: ", Style::default().fg(Color::Cyan)),
+        Span::raw(summary),
+    ]));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:>8}  {:<16} {:<13} {:>10}  {}",
+            "TID", "NAME", "STATE", "CPU TIME", "WCHAN"
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    for thread in &result.threads {
+        let state_style = match thread.state {
+            'R' => Style::default().fg(Color::Green),
+            'D' => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            'Z' | 'T' | 't' => Style::default().fg(Color::Magenta),
+            _ => Style::default().fg(Color::Gray),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:>8}  ", thread.tid),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(format!("{:<16} ", thread.name)),
+            Span::styled(
+                format!(
+                    "{:<13} ",
+                    format!("{} {}", thread.state, state_description(thread.state))
+                ),
+                state_style,
+            ),
+            Span::raw(format!("{:>9.2}s  ", thread.cpu_time)),
+            Span::styled(
+                thread.wchan.as_deref().unwrap_or("-"),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+    }
+
+    lines
+}
+
+/// Clear and return a centered popup area
+fn popup_area(area: Rect, width: u16, height: u16, buf: &mut Buffer) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    for py in popup.y..popup.y + popup.height {
+        for px in popup.x..popup.x + popup.width {
+            if let Some(cell) = buf.cell_mut((px, py)) {
+                cell.reset();
+                cell.set_bg(Color::Black);
+            }
+        }
     }
+
+    popup
+}
+
+/// Render the signal picker popup
+fn render_signal_picker(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let popup = popup_area(area, 56, Signal::ALL.len() as u16 + 4, buf);
+
+    let items: Vec<ListItem> = Signal::ALL
+        .iter()
+        .map(|sig| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<9}", sig.name()),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(sig.description(), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(" Send Signal ")
+                .title_bottom(" [Enter] Select  [Esc] Cancel ")
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::Cyan).bg(Color::Black)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+
+    let mut list_state = ListState::default().with_selected(Some(state.signal_selected));
+    StatefulWidget::render(list, popup, buf, &mut list_state);
+}
+
+/// Render the renice prompt popup
+fn render_renice_prompt(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let popup = popup_area(area, 44, 5, buf);
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(" Nice (-20..19): ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}█", state.renice_input),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(Span::styled(
+            " Lower values need root",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(" Renice ")
+                .title_bottom(" [Enter] Apply  [Esc] Cancel ")
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::Cyan).bg(Color::Black)),
+        )
+        .render(popup, buf);
+}
+
+/// Render the confirmation dialog for a pending action
+fn render_confirm_dialog(state: &ProcessTracerState, area: Rect, buf: &mut Buffer) {
+    let Some(ref pending) = state.pending_action else {
+        return;
+    };
+
+    let popup = popup_area(area, 70, 8, buf);
+    let label = |text| Span::styled(text, Style::default().fg(Color::Cyan));
+
+    let lines = vec![
+        Line::from(vec![
+            label(" Action:  "),
+            Span::styled(
+                pending.action.describe(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            label(" PID:     "),
+            Span::raw(pending.pid.to_string()),
+        ]),
+        Line::from(vec![label(" Command: "), Span::raw(&pending.command)]),
+        Line::from(vec![label(" User:    "), Span::raw(&pending.user)]),
+    ];
+
+    Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(" Confirm ")
+                .title_bottom(" [y] Confirm  [any other key] Cancel ")
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(Color::Yellow).bg(Color::Black)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .render(popup, buf);
 }
 
 /// Render notification popup
@@ -0,0 +1,110 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// UID → user name table from /etc/passwd (loaded once)
+static USERS: OnceLock<HashMap<u32, String>> = OnceLock::new();
+
+/// GID → group name table from /etc/group (loaded once)
+static GROUPS: OnceLock<HashMap<u32, String>> = OnceLock::new();
+
+/// Parse a passwd/group style file: "name:x:ID:..." → ID → name
+fn parse_id_file(path: &str) -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+
+    if let Ok(content) = std::fs::read_to_string(path) {
+        for line in content.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split(':');
+            let (Some(name), Some(_), Some(id)) = (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if let Ok(id) = id.parse::<u32>() {
+                // First entry wins, like getpwuid()
+                names.entry(id).or_insert_with(|| name.to_string());
+            }
+        }
+    }
+
+    names
+}
+
+fn users() -> &'static HashMap<u32, String> {
+    USERS.get_or_init(|| parse_id_file("/etc/passwd"))
+}
+
+fn groups() -> &'static HashMap<u32, String> {
+    GROUPS.get_or_init(|| parse_id_file("/etc/group"))
+}
+
+/// Resolve a UID to a user name, falling back to the numeric ID
+pub fn user_name(uid: u32) -> String {
+    users()
+        .get(&uid)
+        .cloned()
+        .unwrap_or_else(|| uid.to_string())
+}
+
+/// Home directory of a user, from /etc/passwd
+pub fn home_dir(uid: u32) -> Option<PathBuf> {
+    let content = std::fs::read_to_string("/etc/passwd").ok()?;
+    content.lines().find_map(|line| {
+        // name:password:UID:GID:GECOS:home:shell
+        let fields: Vec<&str> = line.split(':').collect();
+        (fields.len() >= 6 && fields[2].parse() == Ok(uid) && !fields[5].is_empty())
+            .then(|| PathBuf::from(fields[5]))
+    })
+}
+
+/// Resolve a GID to a group name, falling back to the numeric ID
+pub fn group_name(gid: u32) -> String {
+    groups()
+        .get(&gid)
+        .cloned()
+        .unwrap_or_else(|| gid.to_string())
+}
+
+/// Real, effective, saved and filesystem IDs of a process (from /proc/[pid]/status)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct IdSet {
+    pub real: u32,
+    pub effective: u32,
+    pub saved: u32,
+    pub filesystem: u32,
+}
+
+impl IdSet {
+    /// Check whether all IDs are the same
+    pub fn is_uniform(&self) -> bool {
+        self.real == self.effective && self.real == self.saved && self.real == self.filesystem
+    }
+
+    /// All four IDs: real, effective, saved, filesystem
+    pub fn ids(&self) -> [u32; 4] {
+        [self.real, self.effective, self.saved, self.filesystem]
+    }
+
+    /// Check whether any ID equals the given one
+    pub fn contains(&self, id: u32) -> bool {
+        self.ids().contains(&id)
+    }
+
+    /// Describe the set with a resolver: "alice" or "real=alice effective=root saved=root"
+    pub fn describe(&self, resolve: fn(u32) -> String) -> String {
+        if self.is_uniform() {
+            format!("{} ({})", resolve(self.real), self.real)
+        } else {
+            format!(
+                "real={} effective={} saved={} fs={}",
+                resolve(self.real),
+                resolve(self.effective),
+                resolve(self.saved),
+                resolve(self.filesystem)
+            )
+        }
+    }
+}